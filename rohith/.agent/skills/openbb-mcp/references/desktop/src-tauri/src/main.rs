@@ -19,15 +19,28 @@ use tauri_plugin_dialog::DialogExt;
 extern crate winapi;
 
 use crate::tauri_handlers::startup::{
-    abort_installation, create_default_backend_services, get_installation_status, install_conda,
-    install_to_directory, setup_python_environment,
+    abort_installation, benchmark_mirrors, check_storage_location, create_default_backend_services,
+    get_installation_status, install_conda, install_to_directory, setup_python_environment,
 };
 
 use crate::tauri_handlers::environments::{
-    create_environment, create_environment_from_requirements, execute_in_environment,
-    get_environment_extensions, install_extensions, list_conda_environments, remove_environment,
-    remove_extension, select_requirements_file, update_environment, update_extension,
-    update_installation_error,
+    EnvironmentListCache, audit_environments_for_api, cancel_environment_operation,
+    check_conda_volumes, check_openbb_extensions_outdated, check_pip_conda_interop,
+    check_python_version_consistency, clean_conda_cache, clean_pip_cache, clear_repodata_cache,
+    clone_environment, create_environment, create_environment_detailed,
+    create_environment_from_lock, create_environment_from_requirements, detect_case_conflicts,
+    detect_conda_on_path, ensure_platform_api, environment_fingerprint,
+    execute_argv_in_environment, execute_in_environment, export_environment_lock,
+    export_environment_requirements, fix_environments_missing_api, gc_environment,
+    generate_environment_manifest, get_environment_channels, get_environment_extensions,
+    get_environment_size, get_preserve_ansi_logs, get_repodata_cache_info,
+    import_external_environment, install_extensions, install_local_editable,
+    list_conda_environments, list_conda_environments_cached_impl, list_outdated_packages,
+    migrate_environment_store, normalize_python_version, preview_install_extensions,
+    refresh_environments, remove_environment, remove_extension, replay_failed_build,
+    reset_environment_to_spec, search_package, select_requirements_file,
+    set_aggressive_update_packages, set_preserve_ansi_logs, set_repodata_cache_ttl,
+    update_environment, update_extension, update_installation_error,
 };
 
 use crate::tauri_handlers::jupyter::{
@@ -36,28 +49,51 @@ use crate::tauri_handlers::jupyter::{
 };
 
 use crate::tauri_handlers::credentials::{
-    get_user_credentials, open_credentials_file, update_user_credentials,
+    get_credential_backup_config, get_user_credentials, list_credential_backups,
+    open_credentials_file, restore_credential_backup, set_credential_backup_config,
+    test_credential, update_user_credentials,
 };
 
 use crate::tauri_handlers::backends::{
-    create_backend_service, delete_backend_service, initialize_backends, list_backend_services,
-    open_backend_logs_window, start_backend_service, stop_backend_service, update_backend_service,
+    BackendStartQueue, create_backend_service, delete_backend_service, export_backend_services,
+    import_backend_services, initialize_backends, list_backend_services, open_backend_logs_window,
+    run_smoke_test, set_max_concurrent_backends, start_backend_service, stop_backend_service,
+    update_backend_service, validate_backend_service,
 };
 
-use crate::utils::certs::generate_self_signed_cert;
+use crate::utils::autostart::{
+    Autostart, AutostartMenuItem, MINIMIZED_ARG, PlatformAutostart, get_autostart_status,
+    launched_minimized, set_autostart, set_autostart_for_app, set_launch_minimized,
+};
+use crate::utils::background_activity::set_background_activity;
+use crate::utils::certs::{ensure_valid_cert, generate_self_signed_cert, inspect_cert};
+use crate::utils::health_events::set_health_debounce;
+use crate::utils::instance_lock::check_instance_lock;
+use crate::utils::maintenance::{MaintenanceMode, get_maintenance_status};
+use crate::utils::sentinel_flags::{self, cleanup_stale_flags};
+use crate::utils::settings_watcher::SettingsWatcher;
+use crate::utils::shutdown_scheduler::{ShutdownScheduler, schedule_shutdown_impl};
+use crate::utils::updater::{
+    ProgressThrottle, build_updater, check_for_update, clear_pending_update, diagnose_updater,
+    get_update_channel, remind_update_later, set_update_channel, skip_update_version,
+    take_pending_update, update_dialog_message, update_prompt_suppressed,
+};
 
 use crate::tauri_handlers::helpers::{
-    check_directory_exists, check_file_exists, get_home_directory, get_installation_directory,
-    get_or_create_app_id, get_settings_directory, get_userdata_directory, get_working_directory,
-    open_url_in_window, open_workspace_in_browser, save_working_directory, select_directory,
-    select_file, toggle_theme, update_openbb_settings,
+    add_allowed_url_host, check_directory_exists, check_disk_space, check_file_exists,
+    follow_os_theme, get_allowed_url_hosts, get_conda_process_priority, get_home_directory,
+    get_installation_directory, get_settings_directory, get_theme, get_userdata_directory,
+    get_working_directory, open_url_in_window, open_workspace_in_browser, remove_allowed_url_host,
+    repair_directory_permissions, rotate_app_id, save_working_directory, select_directory,
+    select_file, select_files, set_conda_process_priority, toggle_theme, update_openbb_settings,
+    verify_binary_integrity,
 };
 
-use tauri_plugin_updater::UpdaterExt;
-
 use crate::utils::process_monitor::{
-    GetProcessLogsRequest, LogEntry, LogStorage, RunningProcesses, get_log_storage,
-    get_process_logs, init_process_monitoring, register_process, unregister_process,
+    GetProcessLogsRequest, LogEntry, LogLevel, LogStorage, RunningProcesses,
+    export_process_logs_window_impl, get_log_file_path, get_log_storage, get_process_logs,
+    get_process_stats, init_process_monitoring, register_process, set_default_log_capacity,
+    unregister_process,
 };
 
 use crate::uninstall::uninstall_application;
@@ -81,172 +117,270 @@ fn unregister_process_monitoring(state: State<ProcessLogState>, process_id: Stri
     unregister_process(&state.0, &process_id)
 }
 
+/// Empty the stored logs of a process, or of all of them for "*", and tell the UI to
+/// clear its view
+#[tauri::command]
+fn clear_process_logs(
+    app_handle: AppHandle,
+    state: State<ProcessLogState>,
+    process_id: String,
+) -> bool {
+    use tauri::Emitter;
+
+    let cleared = crate::utils::process_monitor::clear_process_logs(&state.0, &process_id);
+    if cleared {
+        let _ = app_handle.emit(
+            "process-logs-cleared",
+            serde_json::json!({ "processId": process_id }),
+        );
+    }
+    cleared
+}
+
+#[tauri::command]
+fn set_log_capacity(
+    state: State<ProcessLogState>,
+    process_id: String,
+    capacity: usize,
+) -> Result<(), String> {
+    crate::utils::process_monitor::set_log_capacity(&state.0, &process_id, capacity)
+}
+
+#[tauri::command]
+fn get_log_stats(
+    state: State<ProcessLogState>,
+    process_id: String,
+) -> Option<crate::utils::process_monitor::LogStats> {
+    crate::utils::process_monitor::get_log_stats(&state.0, &process_id)
+}
+
 #[tauri::command]
 fn get_process_logs_history(
     state: State<ProcessLogState>,
     process_id: String,
     count: Option<usize>,
+    level: Option<LogLevel>,
+    contains: Option<String>,
 ) -> Vec<LogEntry> {
-    let request = GetProcessLogsRequest { process_id, count };
+    let request = GetProcessLogsRequest {
+        process_id,
+        count,
+        level,
+        contains,
+    };
     get_process_logs(&state.0.clone(), request)
 }
 
-async fn check_and_apply_update(app: AppHandle, always_prompt: bool) {
-    let show_error = |app: &AppHandle, title: &str, message: String| {
-        app.dialog()
-            .message(message)
-            .title(title)
-            .kind(tauri_plugin_dialog::MessageDialogKind::Error)
-            .show(|_| {});
-    };
-    let update_url = "https://github.com/OpenBB-finance/OpenBB/releases/download/ODP/latest.json";
+#[tauri::command]
+fn export_process_logs_json(
+    state: State<ProcessLogState>,
+    process_id: String,
+) -> Result<String, String> {
+    crate::utils::process_monitor::export_process_logs_json_impl(&state.0, &process_id)
+}
 
-    let ap_id = get_or_create_app_id();
+#[tauri::command]
+fn export_process_logs_text(
+    state: State<ProcessLogState>,
+    process_id: String,
+) -> Result<String, String> {
+    crate::utils::process_monitor::export_process_logs_text_impl(&state.0, &process_id)
+}
 
-    let headers = {
-        let mut headers = reqwest::header::HeaderMap::new();
+#[tauri::command]
+fn export_process_logs_window(
+    state: State<ProcessLogState>,
+    process_id: String,
+    since: i64,
+    until: i64,
+    out_path: String,
+) -> Result<usize, String> {
+    export_process_logs_window_impl(
+        &state.0,
+        &process_id,
+        since,
+        until,
+        std::path::Path::new(&out_path),
+        &crate::tauri_handlers::helpers::RealFileSystem,
+    )
+}
 
-        match reqwest::header::HeaderValue::from_str("ODP-Updater") {
-            Ok(user_agent) => {
-                headers.insert(reqwest::header::USER_AGENT, user_agent);
-            }
-            Err(e) => {
-                log::error!("Failed to create User-Agent header: {}", e);
-                if always_prompt {
-                    show_error(
-                        &app,
-                        "Update Check Failed",
-                        format!("Failed to create User-Agent header: {}", e),
-                    );
-                }
-                return;
-            }
+const UPDATE_INSTALL_LABEL: &str = "Install";
+const UPDATE_SKIP_LABEL: &str = "Skip This Version";
+const UPDATE_REMIND_LABEL: &str = "Remind Me Later";
+
+// Download and install `update`, reporting progress to the UI, then restart into it
+async fn install_update(
+    app: &AppHandle,
+    update: tauri_plugin_updater::Update,
+) -> Result<(), String> {
+    use tauri::Emitter;
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    // Let the UI draw a progress bar while the download runs
+    let progress_handle = app.clone();
+    let finished_handle = app.clone();
+    let mut throttle = ProgressThrottle::default();
+    let on_chunk = move |chunk: usize, total: Option<u64>| {
+        if let Some(progress) = throttle.advance(chunk as u64, total) {
+            let _ = progress_handle.emit("update-progress", progress);
         }
+    };
+    let on_finished = move || {
+        let _ = finished_handle.emit("update-download-finished", ());
+    };
 
-        match reqwest::header::HeaderValue::from_str(&ap_id) {
-            Ok(app_id) => {
-                headers.insert(reqwest::header::HeaderName::from_static("x-app-id"), app_id);
-            }
-            Err(e) => {
-                log::error!("Failed to create X-App-ID header: {}", e);
-                if always_prompt {
-                    show_error(
-                        &app,
-                        "Update Check Failed",
-                        format!("Failed to create X-App-ID header: {}", e),
-                    );
-                }
-                return;
-            }
+    update
+        .download_and_install(on_chunk, on_finished)
+        .await
+        .map_err(|e| {
+            // Don't retry a download that failed; the next install checks again
+            clear_pending_update();
+            format!("Failed to install update: {e}")
+        })?;
+    log::info!("Update installed successfully, restarting...");
+
+    // SET THE FLAG TO SHOW WINDOW AFTER RESTART
+    if sentinel_flags::set_flag(sentinel_flags::SHOW_ON_RESTART).is_ok() {
+        log::info!("Set flag to show window on restart");
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+    }
+
+    app.request_restart();
+
+    /* #[cfg(not(target_os = "macos"))]
+    {
+        use std::process::Command;
+
+        if let Ok(exe_path) = std::env::current_exe() {
+            log::debug!("Relaunching from: {}", exe_path.display());
+            let _ = Command::new(exe_path).spawn();
+            std::process::exit(0);
+        } else {
+            log::error!("Could not determine executable path for restart");
+            app.restart();
         }
+    }
+    */
+    Ok(())
+}
 
-        headers
+/// Install the update found by the last `check_for_update`, or by a fresh check if
+/// there was none, and restart into it
+#[tauri::command]
+async fn apply_update(app: AppHandle) -> Result<(), String> {
+    let update = match take_pending_update() {
+        Some(update) => update,
+        None => build_updater(&app)?
+            .check()
+            .await
+            .map_err(|e| format!("Failed to check for updates: {e}"))?
+            .ok_or("No update available")?,
     };
+    install_update(&app, update)
+        .await
+        .inspect_err(|e| log::error!("{e}"))
+}
 
-    let url = match update_url.parse() {
-        Ok(url) => url,
+async fn check_and_apply_update(app: AppHandle, always_prompt: bool) {
+    let show_error = |app: &AppHandle, title: &str, message: String| {
+        app.dialog()
+            .message(message)
+            .title(title)
+            .kind(tauri_plugin_dialog::MessageDialogKind::Error)
+            .show(|_| {});
+    };
+    let updater = match build_updater(&app) {
+        Ok(updater) => updater,
         Err(e) => {
-            let err_msg = format!("Failed to parse update URL: {}", e);
-            log::error!("{}", err_msg);
+            log::error!("{}", e);
             if always_prompt {
-                show_error(&app, "Update Check Failed", err_msg);
+                show_error(&app, "Update Check Failed", e);
             }
             return;
         }
     };
 
-    let updater_res = app
-        .updater_builder()
-        .headers(headers)
-        .endpoints(vec![url])
-        .and_then(|builder| builder.build());
-
-    match updater_res {
-        Ok(updater) => {
-            match updater.check().await {
-                Ok(Some(update)) => {
-                    let app_clone = app.clone();
-                    app.dialog()
-                    .message(format!(
-                        "A new version ({}) is available. Would you like to install it now? The update will close the application and restart.",
-                        update.version
-                    ))
-                    .title("Update Available")
-                    .kind(tauri_plugin_dialog::MessageDialogKind::Info)
-                    .buttons(tauri_plugin_dialog::MessageDialogButtons::YesNo)
-                    .show(move |install| {
-                        if install {
-                            let app_clone_inner = app_clone.clone();
-                            tauri::async_runtime::spawn(async move {
-                                if let Some(window) = app_clone_inner.get_webview_window("main") {
-                                    let _ = window.show();
-                                    let _ = window.set_focus();
-                                }
+    match updater.check().await {
+        Ok(Some(update)) => {
+            // Checking from the menu always shows the update, even a skipped one
+            if !always_prompt && update_prompt_suppressed(&update.version) {
+                log::debug!("Update {} available, prompt suppressed", update.version);
+                return;
+            }
 
-                                if let Err(e) = update.download_and_install(|_, _| {}, || {}).await
-                                {
-                                    log::error!("Failed to install update: {}", e);
-                                    show_error(
-                                        &app_clone_inner,
-                                        "Update Failed",
-                                        format!("Failed to install update: {}", e),
-                                    );
-                                } else {
-                                    log::info!("Update installed successfully, restarting...");
-
-                                    // SET THE FLAG TO SHOW WINDOW AFTER RESTART
-                                    if let Ok(home_dir) = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")) {
-                                        let flag_path = std::path::Path::new(&home_dir).join(".openbb_platform").join(".show_on_restart");
-                                        let _ = std::fs::write(flag_path, "1");
-                                        log::info!("Set flag to show window on restart");
-                                    }
-
-                                    if let Some(window) = app_clone_inner.get_webview_window("main") {
-                                        let _ = window.show();
-                                    }
-
-                                    app_clone_inner.request_restart();
-
-                                    /* #[cfg(not(target_os = "macos"))]
-                                    {
-                                        use std::process::Command;
-
-                                        if let Ok(exe_path) = std::env::current_exe() {
-                                            log::debug!("Relaunching from: {}", exe_path.display());
-                                            let _ = Command::new(exe_path).spawn();
-                                            std::process::exit(0);
-                                        } else {
-                                            log::error!("Could not determine executable path for restart");
-                                            app_clone_inner.restart();
-                                        }
-                                    }
-                                    */
-                                }
-                            });
+            let app_clone = app.clone();
+            app.dialog()
+                .message(update_dialog_message(
+                    &update.version,
+                    update.body.as_deref(),
+                ))
+                .title("Update Available")
+                .kind(tauri_plugin_dialog::MessageDialogKind::Info)
+                .buttons(
+                    tauri_plugin_dialog::MessageDialogButtons::YesNoCancelCustom(
+                        UPDATE_INSTALL_LABEL.to_string(),
+                        UPDATE_SKIP_LABEL.to_string(),
+                        UPDATE_REMIND_LABEL.to_string(),
+                    ),
+                )
+                .show_with_result(move |result| {
+                    use tauri_plugin_dialog::MessageDialogResult;
+
+                    // Platforms without custom labels report the standard buttons
+                    let choice = match result {
+                        MessageDialogResult::Custom(label) => label,
+                        MessageDialogResult::Yes | MessageDialogResult::Ok => {
+                            UPDATE_INSTALL_LABEL.to_string()
                         }
-                    });
-                }
-                Ok(None) => {
-                    log::debug!("No updates available");
-                    if always_prompt {
-                        app.dialog()
-                            .message("You are already running the latest version.")
-                            .title("No Updates Available")
-                            .kind(tauri_plugin_dialog::MessageDialogKind::Info)
-                            .show(|_| {});
-                    }
-                }
-                Err(e) => {
-                    let err_msg = format!("Failed to check for updates: {}", e);
-                    log::error!("{}", err_msg);
-                    if always_prompt {
-                        show_error(&app, "Update Check Failed", err_msg);
+                        MessageDialogResult::No => UPDATE_SKIP_LABEL.to_string(),
+                        _ => UPDATE_REMIND_LABEL.to_string(),
+                    };
+                    let install = match choice.as_str() {
+                        UPDATE_INSTALL_LABEL => true,
+                        UPDATE_SKIP_LABEL => {
+                            if let Err(e) = skip_update_version(&update.version) {
+                                log::warn!("Failed to skip update: {e}");
+                            }
+                            false
+                        }
+                        // Remind me later, or the dialog was dismissed
+                        _ => {
+                            if let Err(e) = remind_update_later() {
+                                log::warn!("Failed to defer update: {e}");
+                            }
+                            false
+                        }
+                    };
+                    if install {
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = install_update(&app_clone, update).await {
+                                log::error!("{e}");
+                                show_error(&app_clone, "Update Failed", e);
+                            }
+                        });
                     }
-                }
+                });
+        }
+        Ok(None) => {
+            log::debug!("No updates available");
+            if always_prompt {
+                app.dialog()
+                    .message("You are already running the latest version.")
+                    .title("No Updates Available")
+                    .kind(tauri_plugin_dialog::MessageDialogKind::Info)
+                    .show(|_| {});
             }
         }
         Err(e) => {
-            let err_msg = format!("Failed to build updater: {}", e);
+            let err_msg = format!("Failed to check for updates: {}", e);
             log::error!("{}", err_msg);
             if always_prompt {
                 show_error(&app, "Update Check Failed", err_msg);
@@ -410,10 +544,54 @@ async fn quit_application(app_handle: AppHandle) {
     app_handle.exit(0);
 }
 
+#[tauri::command]
+async fn schedule_shutdown(
+    app_handle: AppHandle,
+    delay_secs: u64,
+    scheduler: State<'_, ShutdownScheduler>,
+) -> Result<(), String> {
+    use tauri::Emitter;
+
+    let tick_handle = app_handle.clone();
+    schedule_shutdown_impl(
+        &scheduler,
+        delay_secs,
+        move |remaining| {
+            let _ = tick_handle.emit(
+                "shutdown-countdown",
+                serde_json::json!({ "remainingSecs": remaining }),
+            );
+        },
+        move || async move {
+            cleanup_all_processes(app_handle.clone()).await;
+            app_handle.exit(0);
+        },
+    )
+}
+
+#[tauri::command]
+fn cancel_scheduled_shutdown(
+    app_handle: AppHandle,
+    scheduler: State<'_, ShutdownScheduler>,
+) -> bool {
+    use tauri::Emitter;
+
+    let cancelled = scheduler.cancel();
+    if cancelled {
+        log::info!("Scheduled shutdown cancelled");
+        let _ = app_handle.emit("shutdown-cancelled", ());
+    }
+    cancelled
+}
+
 async fn cleanup_all_processes(app_handle: AppHandle) {
     use crate::tauri_handlers::helpers::{RealEnvSystem, RealFileExtTrait, RealFileSystem};
     log::debug!("Running complete application cleanup");
 
+    if let Some(watcher) = app_handle.try_state::<SettingsWatcher>() {
+        watcher.stop();
+    }
+
     let cleanup_timeout = std::time::Duration::from_secs(10);
 
     let cleanup_result = tokio::time::timeout(cleanup_timeout, async {
@@ -451,6 +629,8 @@ async fn cleanup_all_processes(app_handle: AppHandle) {
         Err(_) => log::warn!("Cleanup process timed out after 10 seconds"),
     }
 
+    utils::instance_lock::release_instance_lock();
+
     #[cfg(target_os = "windows")]
     {
         log::debug!("Waiting for Windows to clean up UI resources...");
@@ -463,6 +643,7 @@ async fn cleanup_all_processes(app_handle: AppHandle) {
 fn main() {
     let _ = fix_path_env::fix();
     init_process_monitoring();
+    utils::instance_lock::acquire_instance_lock();
 
     tauri::Builder::default()
         .plugin(tauri_plugin_updater::Builder::new().build())
@@ -482,9 +663,15 @@ fn main() {
         .plugin(tauri_plugin_dialog::init())
         .manage(ProcessLogState(get_log_storage()))
         .manage(RunningProcesses::new())
+        .manage(BackendStartQueue::default())
+        .manage(EnvironmentListCache::default())
+        .manage(ShutdownScheduler::default())
+        .manage(MaintenanceMode::default())
+        .manage(SettingsWatcher::default())
         .manage(check_installation_on_startup())
         .invoke_handler(tauri::generate_handler![
             toggle_theme,
+            get_theme,
             navigate_to_page,
             save_working_directory,
             get_working_directory,
@@ -494,26 +681,71 @@ fn main() {
             get_userdata_directory,
             get_settings_directory,
             select_file,
+            select_files,
             install_to_directory,
+            check_storage_location,
+            benchmark_mirrors,
             check_directory_exists,
             check_file_exists,
+            check_disk_space,
+            get_conda_process_priority,
+            set_conda_process_priority,
             install_conda,
             abort_installation,
             get_installation_status,
             get_installation_state,
             setup_python_environment,
             create_environment,
+            create_environment_detailed,
+            cancel_environment_operation,
+            replay_failed_build,
             list_conda_environments,
+            refresh_environments,
             get_environment_extensions,
+            get_environment_size,
+            export_environment_requirements,
+            get_environment_channels,
+            generate_environment_manifest,
+            search_package,
+            list_outdated_packages,
+            check_python_version_consistency,
+            check_pip_conda_interop,
+            clean_conda_cache,
+            clean_pip_cache,
+            gc_environment,
+            check_openbb_extensions_outdated,
             install_extensions,
+            preview_install_extensions,
+            install_local_editable,
             update_extension,
             update_environment,
+            set_aggressive_update_packages,
+            get_repodata_cache_info,
+            set_repodata_cache_ttl,
+            clear_repodata_cache,
             update_installation_error,
             remove_extension,
             remove_environment,
+            clone_environment,
+            export_environment_lock,
+            environment_fingerprint,
+            create_environment_from_lock,
             create_environment_from_requirements,
+            normalize_python_version,
+            import_external_environment,
             select_requirements_file,
             execute_in_environment,
+            execute_argv_in_environment,
+            ensure_platform_api,
+            audit_environments_for_api,
+            fix_environments_missing_api,
+            get_preserve_ansi_logs,
+            set_preserve_ansi_logs,
+            migrate_environment_store,
+            detect_case_conflicts,
+            detect_conda_on_path,
+            check_conda_volumes,
+            reset_environment_to_spec,
             start_jupyter_server,
             stop_jupyter_server,
             stop_all_jupyter_servers,
@@ -521,43 +753,88 @@ fn main() {
             list_jupyter_servers,
             get_user_credentials,
             open_credentials_file,
+            test_credential,
             update_user_credentials,
+            get_credential_backup_config,
+            set_credential_backup_config,
+            list_credential_backups,
+            restore_credential_backup,
             open_url_in_window,
+            rotate_app_id,
+            repair_directory_permissions,
+            get_allowed_url_hosts,
+            add_allowed_url_host,
+            remove_allowed_url_host,
             register_process_monitoring,
             unregister_process_monitoring,
             get_process_logs_history,
+            clear_process_logs,
+            set_log_capacity,
+            set_default_log_capacity,
+            get_log_stats,
+            get_log_file_path,
+            get_process_stats,
+            export_process_logs_window,
+            export_process_logs_json,
+            export_process_logs_text,
             open_jupyter_logs_window,
             update_jupyter_status,
             open_backend_logs_window,
             start_backend_service,
             stop_backend_service,
             update_backend_service,
+            validate_backend_service,
             create_backend_service,
+            export_backend_services,
+            import_backend_services,
             delete_backend_service,
             list_backend_services,
+            set_max_concurrent_backends,
+            run_smoke_test,
             uninstall_application,
             quit_application,
+            schedule_shutdown,
+            cancel_scheduled_shutdown,
             generate_self_signed_cert,
+            ensure_valid_cert,
+            inspect_cert,
             update_openbb_settings,
+            verify_binary_integrity,
+            set_background_activity,
+            get_autostart_status,
+            set_autostart,
+            set_launch_minimized,
+            set_health_debounce,
+            diagnose_updater,
+            get_update_channel,
+            set_update_channel,
+            check_for_update,
+            apply_update,
+            get_maintenance_status,
+            check_instance_lock,
+            cleanup_stale_flags,
             create_default_backend_services
         ])
         .setup(|app_handle| {
             let install_state = check_installation_on_startup();
 
-            let show_after_update = {
-                if let Ok(home_dir) = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")) {
-                    let flag_path = std::path::Path::new(&home_dir).join(".openbb_platform").join(".show_on_restart");
-                    if flag_path.exists() {
-                        log::info!("Found update restart flag - will show window");
-                        let _ = std::fs::remove_file(&flag_path);
-                        true
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
-            };
+            // Flags older than a few minutes were left behind by a crash and are dropped unacted
+            let show_after_update = sentinel_flags::consume_flag(sentinel_flags::SHOW_ON_RESTART);
+            if show_after_update {
+                log::info!("Found update restart flag - will show window");
+            }
+            cleanup_stale_flags();
+
+            // Tell the UI when the settings files are edited outside the app
+            if let Err(e) = app_handle.state::<SettingsWatcher>().start(app_handle.handle().clone()) {
+                log::warn!("Settings changes won't be picked up: {e}");
+            }
+
+            // Login launches registered with "start minimized" stay in the tray
+            let start_minimized = launched_minimized();
+            if start_minimized {
+                log::info!("Launched with {MINIMIZED_ARG} - keeping the window hidden");
+            }
 
             if install_state.is_installed {
                 let backend_handle = app_handle.handle().clone();
@@ -565,30 +842,39 @@ fn main() {
                     use crate::tauri_handlers::helpers::{RealFileExtTrait, RealFileSystem, RealEnvSystem};
                     tokio::time::sleep(std::time::Duration::from_millis(100)).await;
                     log::debug!("Initializing backends after state setup delay");
+                    match ensure_valid_cert(backend_handle.clone()).await {
+                        Ok(Some(status)) if status.renewed => log::info!("Renewed certificate {}", status.cert_path),
+                        Ok(_) => {}
+                        Err(e) => log::error!("Failed to check certificate: {e}"),
+                    }
                     if let Err(e) = initialize_backends(&backend_handle, RealFileSystem, RealEnvSystem, RealFileExtTrait).await {
                         log::error!("Failed to initialize backends: {e}");
                     }
                 });
+
+                // Warm the environment list so the first page visit doesn't wait on conda
+                let cache_handle = app_handle.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    use crate::tauri_handlers::helpers::{RealFileSystem, RealEnvSystem, RealFileExtTrait};
+                    let cache = cache_handle.state::<EnvironmentListCache>();
+                    if let Err(e) = list_conda_environments_cached_impl(None, &cache, &RealFileSystem, &RealEnvSystem, &RealFileExtTrait).await {
+                        log::debug!("Could not warm environment list: {e}");
+                    }
+                });
             }
 
+            tauri::async_runtime::spawn_blocking(|| {
+                use crate::tauri_handlers::helpers::{RealEnvSystem, verify_binary_integrity_impl};
+                if let Ok(exe_path) = std::env::current_exe() {
+                    verify_binary_integrity_impl(&exe_path, &RealEnvSystem);
+                }
+            });
+
             if let Some(window) = app_handle.get_webview_window("main") {
                 window.set_menu(Menu::new(app_handle.handle())?)?;
             }
 
-            let autostart_enabled = {
-                #[cfg(target_os = "macos")]
-                {
-                    utils::autostart::macos_autostart::is_autostart_enabled(app_handle.handle()).unwrap_or(true)
-                }
-                #[cfg(target_os = "windows")]
-                {
-                    utils::autostart::windows_autostart::is_autostart_enabled(app_handle.handle()).unwrap_or(true)
-                }
-                #[cfg(target_os = "linux")]
-                {
-                    utils::autostart::linux_autostart::is_autostart_enabled(&app_handle.handle()).unwrap_or(true)
-                }
-            };
+            let autostart_enabled = PlatformAutostart::new(app_handle.handle()).is_enabled().unwrap_or(true);
             log::debug!("Autostart is currently: {}", if autostart_enabled { "enabled" } else { "disabled" });
 
             let handle = app_handle.handle().clone();
@@ -603,6 +889,7 @@ fn main() {
                 .id("start_at_login")
                 .checked(autostart_enabled)
                 .build(&handle)?;
+            app_handle.manage(AutostartMenuItem(start_at_login_item.clone()));
             let separator3 = tauri::menu::PredefinedMenuItem::separator(&handle)?;
             let check_updates_item = MenuItemBuilder::new("Check for Updates").id("check_updates").build(&handle)?;
             let uninstall_item = MenuItemBuilder::new("Uninstall").id("uninstall").build(&handle)?;
@@ -671,60 +958,12 @@ fn main() {
                             }
                         },
                         "start_at_login" => {
-                            let app_handle = tray_handle.clone();
-                            let is_enabled = {
-                                #[cfg(target_os = "macos")]
-                                {
-                                    utils::autostart::macos_autostart::is_autostart_enabled(&app_handle).unwrap_or(false)
-                                }
-                                #[cfg(target_os = "windows")]
-                                {
-                                    utils::autostart::windows_autostart::is_autostart_enabled(&app_handle).unwrap_or(false)
-                                }
-                                #[cfg(target_os = "linux")]
-                                {
-                                    utils::autostart::linux_autostart::is_autostart_enabled(&app_handle).unwrap_or(false)
-                                }
-                            };
+                            let is_enabled = PlatformAutostart::new(&tray_handle).is_enabled().unwrap_or(false);
                             log::debug!("Current autostart status: {}", if is_enabled { "enabled" } else { "disabled" });
-                            let target_state = !is_enabled;
-                            log::debug!("Attempting to {} autostart", if target_state { "enable" } else { "disable" });
-                            let result = {
-                                #[cfg(target_os = "macos")]
-                                {
-                                    if target_state {
-                                        utils::autostart::macos_autostart::enable_autostart(&app_handle)
-                                    } else {
-                                        utils::autostart::macos_autostart::disable_autostart(&app_handle)
-                                    }
-                                }
-                                #[cfg(target_os = "windows")]
-                                {
-                                    if target_state {
-                                        utils::autostart::windows_autostart::enable_autostart(&app_handle)
-                                    } else {
-                                        utils::autostart::windows_autostart::disable_autostart(&app_handle)
-                                    }
-                                }
-                                #[cfg(target_os = "linux")]
-                                {
-                                    if target_state {
-                                        utils::autostart::linux_autostart::enable_autostart(&app_handle)
-                                    } else {
-                                        utils::autostart::linux_autostart::disable_autostart(&app_handle)
-                                    }
-                                }
-                            };
-                            if let Err(e) = result {
-                                log::error!("Failed to {} autostart: {}", if target_state { "enable" } else { "disable" }, e);
-                                return;
+                            match set_autostart_for_app(&tray_handle, !is_enabled) {
+                                Ok(status) => log::debug!("Successfully {} autostart", if status.enabled { "enabled" } else { "disabled" }),
+                                Err(e) => log::error!("{e}"),
                             }
-                            if let Err(e) = start_at_login_item.set_checked(target_state) {
-                                log::error!("Failed to update menu item state: {e}");
-                            } else {
-                                log::debug!("Menu item state updated to: {target_state}");
-                            }
-                            log::debug!("Successfully {} autostart", if target_state { "enabled" } else { "disabled" });
                         }
                         _ => {}
                     }
@@ -736,12 +975,24 @@ fn main() {
 
             if let Some(window) = app_handle.get_webview_window("main") {
                 let window_clone = window.clone();
-                window.on_window_event(move |event| {
-                    if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                window.on_window_event(move |event| match event {
+                    tauri::WindowEvent::CloseRequested { api, .. } => {
                         window_clone.hide().unwrap();
                         api.prevent_close();
+                        utils::background_activity::schedule_auto_pause(window_clone.clone());
+                    }
+                    tauri::WindowEvent::Focused(true) => {
+                        utils::background_activity::background_activity().set_auto_paused(false);
                     }
+                    tauri::WindowEvent::ThemeChanged(theme) => {
+                        tauri::async_runtime::spawn(follow_os_theme(window_clone.app_handle().clone(), *theme));
+                    }
+                    _ => {}
                 });
+                // The OS appearance may have changed while the app was closed
+                if let Ok(theme) = window.theme() {
+                    tauri::async_runtime::spawn(follow_os_theme(app_handle.handle().clone(), theme));
+                }
                 #[cfg(target_os = "macos")]
                 {
                     use objc2_app_kit::{NSColor, NSWindow};
@@ -796,7 +1047,9 @@ fn main() {
                         let _ = window.show();
                     }
 
-                    let _ = window.set_focus();
+                    if !start_minimized {
+                        let _ = window.set_focus();
+                    }
                 }
             }
             Ok(())
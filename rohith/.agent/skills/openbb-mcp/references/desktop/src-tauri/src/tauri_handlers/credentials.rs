@@ -1,4 +1,117 @@
-use crate::tauri_handlers::helpers::{EnvSystem, FileSystem, RealEnvSystem, RealFileSystem};
+use crate::tauri_handlers::environments::{env_python_path, kill_process_tree};
+use crate::tauri_handlers::helpers::{
+    EnvSystem, FileSystem, RealEnvSystem, RealFileSystem, Secret, parse_settings_with_backup,
+    redact, redact_secrets, set_user_preference, stored_credential_secrets, write_with_backup,
+};
+use crate::utils::maintenance::begin_mutating_command;
+use serde::{Deserialize, Serialize};
+
+// Credential keys understood by the OpenBB Platform providers.
+// Keys outside this list are still stored, but reported back as unknown.
+const KNOWN_CREDENTIAL_KEYS: &[&str] = &[
+    "alpha_vantage_api_key",
+    "benzinga_api_key",
+    "biztoc_api_key",
+    "cboe_api_key",
+    "econdb_api_key",
+    "fmp_api_key",
+    "fred_api_key",
+    "intrinio_api_key",
+    "nasdaq_api_key",
+    "polygon_api_key",
+    "tiingo_token",
+    "tmx_api_key",
+    "tradier_api_key",
+    "tradingeconomics_api_key",
+];
+
+// Cheapest authenticated OpenBB call per provider, used to check a credential works.
+// Providers not listed here can't be tested.
+const CREDENTIAL_TESTS: &[(&str, &str)] = &[
+    (
+        "benzinga",
+        r#"obb.news.world(provider="benzinga", limit=1)"#,
+    ),
+    ("fmp", r#"obb.equity.price.quote("AAPL", provider="fmp")"#),
+    ("fred", r#"obb.economy.fred_series("GDP", provider="fred")"#),
+    (
+        "intrinio",
+        r#"obb.equity.price.quote("AAPL", provider="intrinio")"#,
+    ),
+    (
+        "polygon",
+        r#"obb.equity.price.historical("AAPL", provider="polygon")"#,
+    ),
+    (
+        "tiingo",
+        r#"obb.equity.price.historical("AAPL", provider="tiingo")"#,
+    ),
+    (
+        "tradier",
+        r#"obb.equity.price.quote("AAPL", provider="tradier")"#,
+    ),
+];
+
+// Printed by the test snippet so its verdict can be told apart from other output
+const CREDENTIAL_TEST_OK: &str = "OPENBB_CREDENTIAL_TEST_OK";
+const CREDENTIAL_TEST_FAILED: &str = "OPENBB_CREDENTIAL_TEST_FAILED:";
+const CREDENTIAL_TEST_ERROR: &str = "OPENBB_CREDENTIAL_TEST_ERROR:";
+
+// How long a credential test may run before it is killed
+const CREDENTIAL_TEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+// HTTP statuses and exception types that mean the credential itself was rejected
+const CREDENTIAL_REJECTED_STATUSES: &[u16] = &[401, 403];
+const CREDENTIAL_REJECTED_TYPES: &[&str] = &["UnauthorizedError", "AuthenticationError"];
+
+// Lowercase fragments of provider error messages that say the same, for providers
+// that raise a generic exception. Anything else (network, rate limits, provider
+// outages) doesn't say whether the credential works.
+const CREDENTIAL_REJECTED_HINTS: &[&str] = &[
+    "unauthorized",
+    "forbidden",
+    "api key",
+    "apikey",
+    "api_key",
+    "credential",
+    "authenticat",
+];
+
+/// Credential values by key, as stored under `credentials` in user_settings.json
+pub type CredentialMap = std::collections::BTreeMap<String, Secret>;
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct CredentialKeyReport {
+    pub recognized: Vec<String>,
+    pub unknown: Vec<String>,
+}
+
+/// Check that credentials are a flat map of string values, splitting the keys into
+/// recognized and unknown ones
+pub fn validate_credentials(
+    credentials: &serde_json::Value,
+) -> Result<CredentialKeyReport, String> {
+    let map = credentials
+        .as_object()
+        .ok_or_else(|| "Credentials must be a JSON object".to_string())?;
+
+    let mut report = CredentialKeyReport::default();
+    for (key, value) in map {
+        if !value.is_string() {
+            return Err(format!(
+                "Invalid value for credential '{key}': expected a string, got {}",
+                redact(&value.to_string())
+            ));
+        }
+        if KNOWN_CREDENTIAL_KEYS.contains(&key.as_str()) {
+            report.recognized.push(key.clone());
+        } else {
+            report.unknown.push(key.clone());
+        }
+    }
+
+    Ok(report)
+}
 
 pub async fn get_user_credentials_impl<F: FileSystem, E: EnvSystem>(
     fs: &F,
@@ -27,7 +140,7 @@ pub async fn get_user_credentials_impl<F: FileSystem, E: EnvSystem>(
         .map_err(|e| format!("Failed to read user settings: {e}"))?;
 
     // Parse the settings
-    let settings: serde_json::Value = serde_json::from_str(&settings_content)
+    let settings = parse_settings_with_backup(&user_settings_path, &settings_content, fs)
         .map_err(|e| format!("Failed to parse user settings: {e}"))?;
 
     Ok(settings)
@@ -38,13 +151,23 @@ pub async fn get_user_credentials() -> Result<serde_json::Value, String> {
     get_user_credentials_impl(&RealFileSystem, &RealEnvSystem).await
 }
 
-pub async fn update_user_credentials_impl<F: FileSystem, E: EnvSystem>(
+pub async fn update_user_credentials_impl<F: FileSystem, E: EnvSystem, K: BackupKeyStore>(
     credentials: serde_json::Value,
     fs: &F,
     env_sys: &E,
-) -> Result<bool, String> {
+    keys: &K,
+) -> Result<CredentialKeyReport, String> {
     use std::path::Path;
 
+    // Reject malformed payloads before touching the settings file
+    let report = validate_credentials(&credentials)?;
+    if !report.unknown.is_empty() {
+        log::warn!("Storing unrecognized credential keys: {:?}", report.unknown);
+    }
+    // From here on the values only print redacted
+    let credentials: CredentialMap = serde_json::from_value(credentials)
+        .map_err(|e| format!("Invalid credentials: {}", redact(&e.to_string())))?;
+
     let home_dir = env_sys
         .var("HOME")
         .or_else(|_| env_sys.var("USERPROFILE"))
@@ -64,7 +187,7 @@ pub async fn update_user_credentials_impl<F: FileSystem, E: EnvSystem>(
             .read_to_string(&user_settings_path)
             .map_err(|e| format!("Failed to read user settings: {e}"))?;
 
-        serde_json::from_str(&settings_content)
+        parse_settings_with_backup(&user_settings_path, &settings_content, fs)
             .map_err(|e| format!("Failed to parse user settings: {e}"))?
     } else {
         serde_json::json!({})
@@ -72,6 +195,8 @@ pub async fn update_user_credentials_impl<F: FileSystem, E: EnvSystem>(
 
     // Update only the credentials section
     if let Some(obj) = settings.as_object_mut() {
+        let credentials = serde_json::to_value(&credentials)
+            .map_err(|e| format!("Failed to serialize credentials: {e}"))?;
         obj.insert("credentials".to_string(), credentials);
     }
 
@@ -79,15 +204,414 @@ pub async fn update_user_credentials_impl<F: FileSystem, E: EnvSystem>(
     let settings_json = serde_json::to_string_pretty(&settings)
         .map_err(|e| format!("Failed to serialize settings: {e}"))?;
 
-    fs.write(&user_settings_path, settings_json.as_str())
+    write_with_backup(&user_settings_path, &settings_json, fs)
         .map_err(|e| format!("Failed to write user settings: {e}"))?;
 
-    Ok(true)
+    // A failed backup is logged but never fails the save itself
+    let backup_config = credential_backup_config(&settings);
+    if backup_config.enabled
+        && let Err(e) = backup_credentials_impl(
+            &settings["credentials"],
+            &backup_config,
+            chrono::Utc::now(),
+            fs,
+            env_sys,
+            keys,
+        )
+    {
+        log::warn!("Failed to back up credentials: {e}");
+    }
+
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn update_user_credentials(
+    credentials: serde_json::Value,
+    app_handle: tauri::AppHandle,
+) -> Result<CredentialKeyReport, String> {
+    let _operation = begin_mutating_command(&app_handle)?;
+    update_user_credentials_impl(
+        credentials,
+        &RealFileSystem,
+        &RealEnvSystem,
+        &KeychainBackupKeyStore,
+    )
+    .await
+}
+
+/// `preferences.credential_backup` in user_settings.json
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct CredentialBackupConfig {
+    pub enabled: bool,
+    /// Number of most recent backups to keep
+    pub keep: usize,
+    pub encrypt: bool,
+}
+
+impl Default for CredentialBackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keep: 10,
+            encrypt: false,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CredentialBackup {
+    pub file: String,
+    pub created_at: Option<String>,
+    pub encrypted: bool,
+}
+
+const CREDENTIAL_BACKUP_PREFIX: &str = "credentials-";
+const CREDENTIAL_BACKUP_TIME_FORMAT: &str = "%Y%m%dT%H%M%S%.3fZ";
+// Where older versions kept the backup key, next to the backups themselves
+const LEGACY_BACKUP_KEY_FILE: &str = ".backup_key";
+const BACKUP_KEY_SERVICE: &str = "co.openbb.platform";
+const BACKUP_KEY_ACCOUNT: &str = "credential-backup-key";
+
+/// Storage for the AES key of encrypted credential backups, hex encoded
+#[cfg_attr(test, mockall::automock)]
+pub trait BackupKeyStore {
+    fn get(&self) -> Result<Option<String>, String>;
+    fn set(&self, key: &str) -> Result<(), String>;
+}
+
+/// The OS keychain: Keychain on macOS, Credential Manager on Windows and the Secret
+/// Service on Linux
+pub struct KeychainBackupKeyStore;
+
+impl KeychainBackupKeyStore {
+    fn entry() -> Result<keyring::Entry, String> {
+        keyring::Entry::new(BACKUP_KEY_SERVICE, BACKUP_KEY_ACCOUNT)
+            .map_err(|e| format!("Failed to open the keychain: {e}"))
+    }
+}
+
+impl BackupKeyStore for KeychainBackupKeyStore {
+    fn get(&self) -> Result<Option<String>, String> {
+        match Self::entry()?.get_password() {
+            Ok(key) => Ok(Some(key)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(format!("Failed to read backup key from the keychain: {e}")),
+        }
+    }
+
+    fn set(&self, key: &str) -> Result<(), String> {
+        Self::entry()?
+            .set_password(key)
+            .map_err(|e| format!("Failed to store backup key in the keychain: {e}"))
+    }
+}
+
+fn credential_backup_config(settings: &serde_json::Value) -> CredentialBackupConfig {
+    serde_json::from_value(settings["preferences"]["credential_backup"].clone()).unwrap_or_default()
+}
+
+fn credential_backup_dir<E: EnvSystem>(env_sys: &E) -> Result<std::path::PathBuf, String> {
+    let home_dir = env_sys
+        .var("HOME")
+        .or_else(|_| env_sys.var("USERPROFILE"))
+        .map_err(|e| format!("Could not determine home directory: {e}"))?;
+
+    Ok(std::path::Path::new(&home_dir)
+        .join(".openbb_platform")
+        .join("backups")
+        .join("credentials"))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    if hex.len() % 2 != 0 {
+        return Err("Invalid hex string".to_string());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| format!("Invalid hex string: {e}"))
+        })
+        .collect()
+}
+
+// The key lives in the keychain rather than on disk, so copying the backup folder
+// doesn't hand out the means to decrypt it. A key file left by an older version is
+// moved into the keychain; without either, a new key is only made when `create` is set.
+fn credential_backup_key<F: FileSystem, K: BackupKeyStore>(
+    backup_dir: &std::path::Path,
+    create: bool,
+    fs: &F,
+    keys: &K,
+) -> Result<Vec<u8>, String> {
+    if let Some(key) = keys.get()? {
+        return from_hex(key.trim());
+    }
+
+    let legacy_path = backup_dir.join(LEGACY_BACKUP_KEY_FILE);
+    let key = if fs.exists(&legacy_path) {
+        let key = fs
+            .read_to_string(&legacy_path)
+            .map_err(|e| format!("Failed to read backup key: {e}"))?;
+        from_hex(key.trim())?
+    } else if create {
+        let mut key = vec![0u8; 32];
+        openssl::rand::rand_bytes(&mut key)
+            .map_err(|e| format!("Failed to generate backup key: {e}"))?;
+        key
+    } else {
+        return Err("Backup key is missing; encrypted backups cannot be restored".to_string());
+    };
+
+    keys.set(&to_hex(&key))?;
+    if fs.exists(&legacy_path)
+        && let Err(e) = fs.remove_file(&legacy_path.to_string_lossy())
+    {
+        log::warn!("Failed to remove the old backup key file: {e}");
+    }
+    Ok(key)
+}
+
+// Backups hold the same secrets as user_settings.json, so only the owner may read them
+fn restrict_to_owner<F: FileSystem>(path: &std::path::Path, fs: &F) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs.set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    #[cfg(not(unix))]
+    let _ = (path, fs);
+    Ok(())
+}
+
+fn encrypt_backup(plaintext: &str, key: &[u8]) -> Result<String, String> {
+    use openssl::symm::{Cipher, encrypt_aead};
+
+    let mut nonce = [0u8; 12];
+    openssl::rand::rand_bytes(&mut nonce).map_err(|e| format!("Failed to generate nonce: {e}"))?;
+    let mut tag = [0u8; 16];
+    let ciphertext = encrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(&nonce),
+        &[],
+        plaintext.as_bytes(),
+        &mut tag,
+    )
+    .map_err(|e| format!("Failed to encrypt backup: {e}"))?;
+
+    Ok(serde_json::json!({
+        "nonce": to_hex(&nonce),
+        "tag": to_hex(&tag),
+        "data": to_hex(&ciphertext),
+    })
+    .to_string())
+}
+
+fn decrypt_backup(envelope: &str, key: &[u8]) -> Result<String, String> {
+    use openssl::symm::{Cipher, decrypt_aead};
+
+    let envelope: serde_json::Value =
+        serde_json::from_str(envelope).map_err(|e| format!("Failed to parse backup: {e}"))?;
+    let field = |name: &str| from_hex(envelope[name].as_str().unwrap_or_default());
+
+    let plaintext = decrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(&field("nonce")?),
+        &[],
+        &field("data")?,
+        &field("tag")?,
+    )
+    .map_err(|e| format!("Failed to decrypt backup: {e}"))?;
+    String::from_utf8(plaintext).map_err(|e| format!("Backup is not valid UTF-8: {e}"))
+}
+
+/// Write a timestamped backup of the credentials section and prune all but the newest
+/// `config.keep` backups
+pub fn backup_credentials_impl<F: FileSystem, E: EnvSystem, K: BackupKeyStore>(
+    credentials: &serde_json::Value,
+    config: &CredentialBackupConfig,
+    now: chrono::DateTime<chrono::Utc>,
+    fs: &F,
+    env_sys: &E,
+    keys: &K,
+) -> Result<std::path::PathBuf, String> {
+    let backup_dir = credential_backup_dir(env_sys)?;
+    if !fs.exists(&backup_dir) {
+        fs.create_dir_all(&backup_dir)
+            .map_err(|e| format!("Failed to create backup directory: {e}"))?;
+    }
+
+    let plaintext = serde_json::to_string_pretty(credentials)
+        .map_err(|e| format!("Failed to serialize credentials: {e}"))?;
+    let (contents, extension) = if config.encrypt {
+        let key = credential_backup_key(&backup_dir, true, fs, keys)?;
+        (encrypt_backup(&plaintext, &key)?, "enc")
+    } else {
+        (plaintext, "json")
+    };
+
+    // Saves within the same millisecond get a counter so no backup is overwritten
+    let stem = format!(
+        "{CREDENTIAL_BACKUP_PREFIX}{}",
+        now.format(CREDENTIAL_BACKUP_TIME_FORMAT)
+    );
+    let mut backup_path = backup_dir.join(format!("{stem}.{extension}"));
+    let mut counter = 1;
+    while fs.exists(&backup_path) {
+        backup_path = backup_dir.join(format!("{stem}-{counter}.{extension}"));
+        counter += 1;
+    }
+    fs.write(&backup_path, &contents)
+        .map_err(|e| format!("Failed to write credential backup: {e}"))?;
+    if let Err(e) = restrict_to_owner(&backup_path, fs) {
+        let _ = fs.remove_file(&backup_path.to_string_lossy());
+        return Err(format!(
+            "Failed to restrict permissions of credential backup: {e}"
+        ));
+    }
+
+    // Timestamps sort lexically, so everything after the newest `keep` names goes
+    let backups = list_credential_backups_impl(fs, env_sys)?;
+    for backup in backups.iter().skip(config.keep.max(1)) {
+        if let Err(e) = fs.remove_file(&backup_dir.join(&backup.file).to_string_lossy()) {
+            log::warn!(
+                "Failed to remove old credential backup {}: {e}",
+                backup.file
+            );
+        }
+    }
+
+    log::debug!("Backed up credentials to {}", backup_path.display());
+    Ok(backup_path)
+}
+
+/// Credential backups, newest first
+pub fn list_credential_backups_impl<F: FileSystem, E: EnvSystem>(
+    fs: &F,
+    env_sys: &E,
+) -> Result<Vec<CredentialBackup>, String> {
+    let backup_dir = credential_backup_dir(env_sys)?;
+    if !fs.exists(&backup_dir) {
+        return Ok(Vec::new());
+    }
+
+    let mut backups: Vec<CredentialBackup> = fs
+        .read_dir(&backup_dir)
+        .map_err(|e| format!("Failed to read backup directory: {e}"))?
+        .iter()
+        .filter_map(|path| {
+            let file = path.file_name()?.to_str()?.to_string();
+            let (stem, encrypted) = if let Some(stem) = file.strip_suffix(".enc") {
+                (stem, true)
+            } else {
+                (file.strip_suffix(".json")?, false)
+            };
+            let stamp = stem.strip_prefix(CREDENTIAL_BACKUP_PREFIX)?;
+            let stamp = stamp.split('-').next().unwrap_or(stamp);
+            let created_at =
+                chrono::NaiveDateTime::parse_from_str(stamp, CREDENTIAL_BACKUP_TIME_FORMAT)
+                    .ok()
+                    .map(|t| t.and_utc().to_rfc3339());
+            Some(CredentialBackup {
+                file: file.clone(),
+                created_at,
+                encrypted,
+            })
+        })
+        .collect();
+
+    // Compare stems so a same-millisecond "-1" backup sorts after its sibling
+    let stem = |backup: &CredentialBackup| {
+        let file = backup.file.clone();
+        file.rsplit_once('.')
+            .map(|(stem, _)| stem.to_string())
+            .unwrap_or(file)
+    };
+    backups.sort_by_key(|backup| std::cmp::Reverse(stem(backup)));
+    Ok(backups)
+}
+
+/// Replace the current credentials with the contents of a backup
+pub async fn restore_credential_backup_impl<F: FileSystem, E: EnvSystem, K: BackupKeyStore>(
+    file: String,
+    fs: &F,
+    env_sys: &E,
+    keys: &K,
+) -> Result<CredentialKeyReport, String> {
+    let backup = list_credential_backups_impl(fs, env_sys)?
+        .into_iter()
+        .find(|backup| backup.file == file)
+        .ok_or_else(|| format!("Credential backup '{file}' not found"))?;
+
+    let backup_dir = credential_backup_dir(env_sys)?;
+    let contents = fs
+        .read_to_string(&backup_dir.join(&backup.file))
+        .map_err(|e| format!("Failed to read credential backup: {e}"))?;
+    let plaintext = if backup.encrypted {
+        decrypt_backup(
+            &contents,
+            &credential_backup_key(&backup_dir, false, fs, keys)?,
+        )?
+    } else {
+        contents
+    };
+
+    let credentials: serde_json::Value = serde_json::from_str(&plaintext)
+        .map_err(|e| format!("Failed to parse credential backup: {e}"))?;
+    log::info!("Restoring credentials from backup {file}");
+    update_user_credentials_impl(credentials, fs, env_sys, keys).await
+}
+
+pub fn set_credential_backup_config_impl<F: FileSystem, E: EnvSystem>(
+    config: CredentialBackupConfig,
+    fs: &F,
+    env_sys: &E,
+) -> Result<(), String> {
+    if config.keep == 0 {
+        return Err("At least one backup must be kept".to_string());
+    }
+
+    let value =
+        serde_json::to_value(&config).map_err(|e| format!("Failed to serialize config: {e}"))?;
+    set_user_preference("credential_backup", value, fs, env_sys)
+}
+
+#[tauri::command]
+pub async fn get_credential_backup_config() -> Result<CredentialBackupConfig, String> {
+    let settings = get_user_credentials_impl(&RealFileSystem, &RealEnvSystem).await?;
+    Ok(credential_backup_config(&settings))
 }
 
 #[tauri::command]
-pub async fn update_user_credentials(credentials: serde_json::Value) -> Result<bool, String> {
-    update_user_credentials_impl(credentials, &RealFileSystem, &RealEnvSystem).await
+pub async fn set_credential_backup_config(config: CredentialBackupConfig) -> Result<(), String> {
+    set_credential_backup_config_impl(config, &RealFileSystem, &RealEnvSystem)
+}
+
+#[tauri::command]
+pub async fn list_credential_backups() -> Result<Vec<CredentialBackup>, String> {
+    list_credential_backups_impl(&RealFileSystem, &RealEnvSystem)
+}
+
+#[tauri::command]
+pub async fn restore_credential_backup(
+    file: String,
+    app_handle: tauri::AppHandle,
+) -> Result<CredentialKeyReport, String> {
+    let _operation = begin_mutating_command(&app_handle)?;
+    restore_credential_backup_impl(
+        file,
+        &RealFileSystem,
+        &RealEnvSystem,
+        &KeychainBackupKeyStore,
+    )
+    .await
 }
 
 pub async fn open_credentials_file_impl<F: FileSystem, E: EnvSystem>(
@@ -185,10 +709,189 @@ pub async fn open_credentials_file(file_name: Option<String>) -> Result<bool, St
     open_credentials_file_impl(file_name, &RealFileSystem, &RealEnvSystem).await
 }
 
+/// Make one cheap authenticated call to `provider` through OpenBB in `environment`,
+/// returning whether the stored credential was accepted
+pub async fn test_credential_impl<F: FileSystem, E: EnvSystem>(
+    provider: String,
+    environment: String,
+    directory: String,
+    timeout: std::time::Duration,
+    fs: &F,
+    env_sys: &E,
+) -> Result<bool, String> {
+    let provider = provider.trim().to_lowercase();
+    let call = CREDENTIAL_TESTS
+        .iter()
+        .find(|(name, _)| *name == provider)
+        .map(|(_, call)| *call)
+        .ok_or_else(|| format!("Testing credentials for '{provider}' is not supported"))?;
+
+    let conda_dir = std::path::Path::new(&directory).join("conda");
+    let python_path = env_python_path(&conda_dir, &environment, env_sys);
+    if !fs.exists(&python_path) {
+        return Err(format!("Environment '{environment}' does not exist"));
+    }
+
+    // Import failures are left to raise, and of the errors from the call itself only
+    // authentication errors count as a rejected credential
+    let python_tuple = |items: Vec<String>| format!("({},)", items.join(", "));
+    let rejected_statuses = python_tuple(
+        CREDENTIAL_REJECTED_STATUSES
+            .iter()
+            .map(|status| status.to_string())
+            .collect(),
+    );
+    let rejected_types = python_tuple(
+        CREDENTIAL_REJECTED_TYPES
+            .iter()
+            .map(|name| format!("{name:?}"))
+            .collect(),
+    );
+    let rejected_hints = python_tuple(
+        CREDENTIAL_REJECTED_HINTS
+            .iter()
+            .map(|hint| format!("{hint:?}"))
+            .collect(),
+    );
+    let script = format!(
+        r#"from openbb import obb
+
+
+def status_of(error):
+    for source in (error, getattr(error, "response", None)):
+        for name in ("status_code", "status"):
+            value = getattr(source, name, None)
+            if isinstance(value, int):
+                return value
+    return None
+
+
+try:
+    {call}
+except Exception as e:
+    message = str(e).lower()
+    if (
+        status_of(e) in {rejected_statuses}
+        or type(e).__name__ in {rejected_types}
+        or any(hint in message for hint in {rejected_hints})
+    ):
+        print("{CREDENTIAL_TEST_FAILED}", e)
+    else:
+        print("{CREDENTIAL_TEST_ERROR}", type(e).__name__, e)
+else:
+    print("{CREDENTIAL_TEST_OK}")
+"#
+    );
+    // Tests of the same provider can overlap, so each gets its own script
+    let script_path = env_sys.temp_dir().join(format!(
+        "openbb_credential_test_{provider}_{}.py",
+        uuid::Uuid::new_v4().simple()
+    ));
+    fs.write(&script_path, &script)
+        .map_err(|e| format!("Failed to create credential test script: {e}"))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Err(e) = fs.set_permissions(&script_path, std::fs::Permissions::from_mode(0o600)) {
+            let _ = fs.remove_file(&script_path.to_string_lossy());
+            return Err(format!(
+                "Failed to restrict permissions of credential test script: {e}"
+            ));
+        }
+    }
+
+    let mut command = env_sys.new_conda_command(&python_path, &conda_dir);
+    command
+        .arg(&script_path)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+    // Lead a process group so a hung test can be killed along with its children
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let output = match command.spawn() {
+        Ok(child) => {
+            let pid = child.id();
+            let waited = tokio::time::timeout(
+                timeout,
+                tokio::task::spawn_blocking(move || child.wait_with_output()),
+            )
+            .await;
+            match waited {
+                Ok(Ok(output)) => output.map_err(|e| format!("Failed to run credential test: {e}")),
+                Ok(Err(e)) => Err(format!("Failed to run credential test: {e}")),
+                Err(_) => {
+                    if let Err(e) = kill_process_tree(pid, env_sys) {
+                        log::warn!("Failed to stop credential test for '{provider}': {e}");
+                    }
+                    Err(format!(
+                        "Credential test for '{provider}' timed out after {}s",
+                        timeout.as_secs()
+                    ))
+                }
+            }
+        }
+        Err(e) => Err(format!("Failed to run credential test: {e}")),
+    };
+    let _ = fs.remove_file(&script_path.to_string_lossy());
+    let output = output?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.contains(CREDENTIAL_TEST_OK) {
+        return Ok(true);
+    }
+    if let Some(line) = stdout
+        .lines()
+        .find(|line| line.starts_with(CREDENTIAL_TEST_FAILED))
+    {
+        // Provider errors often quote the request URL, key included
+        let line = redact_secrets(line, &stored_credential_secrets(fs, env_sys));
+        log::info!("Credential test for '{provider}' failed: {line}");
+        return Ok(false);
+    }
+    if let Some(line) = stdout
+        .lines()
+        .find(|line| line.starts_with(CREDENTIAL_TEST_ERROR))
+    {
+        let error = redact_secrets(
+            line.trim_start_matches(CREDENTIAL_TEST_ERROR).trim(),
+            &stored_credential_secrets(fs, env_sys),
+        );
+        return Err(format!(
+            "Credential test for '{provider}' could not reach the provider: {error}"
+        ));
+    }
+    Err(format!(
+        "Credential test for '{provider}' did not run: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    ))
+}
+
+#[tauri::command]
+pub async fn test_credential(
+    provider: String,
+    environment: String,
+    directory: String,
+) -> Result<bool, String> {
+    test_credential_impl(
+        provider,
+        environment,
+        directory,
+        CREDENTIAL_TEST_TIMEOUT,
+        &RealFileSystem,
+        &RealEnvSystem,
+    )
+    .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tauri_handlers::helpers::{MockEnvSystem, MockFileSystem};
+    use crate::tauri_handlers::helpers::{
+        FileSystem, InMemoryFS, MockEnvSystem, MockFileSystem, mock_command_echo, mock_home_env,
+    };
     use mockall::predicate::*;
     use std::path::PathBuf;
 
@@ -223,6 +926,174 @@ mod tests {
         }
     }
 
+    fn mock_credential_test(mock_fs: &mut MockFileSystem, mock_env: &mut MockEnvSystem) {
+        mock_env
+            .expect_consts_os()
+            .return_const(if cfg!(windows) { "windows" } else { "unix" });
+        mock_env
+            .expect_temp_dir()
+            .returning(|| PathBuf::from("/mock/tmp"));
+        mock_fs.expect_exists().return_const(true);
+        mock_fs
+            .expect_write()
+            .withf(|path, script| {
+                let file = path.file_name().unwrap().to_string_lossy();
+                file.starts_with("openbb_credential_test_fmp_")
+                    && file.ends_with(".py")
+                    && script.contains(r#"obb.equity.price.quote("AAPL", provider="fmp")"#)
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        #[cfg(unix)]
+        mock_fs
+            .expect_set_permissions()
+            .withf(|_, permissions| {
+                use std::os::unix::fs::PermissionsExt;
+                permissions.mode() & 0o777 == 0o600
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_fs.expect_remove_file().times(1).returning(|_| Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_credential_detects_success_marker() {
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_env = MockEnvSystem::new();
+        mock_credential_test(&mut mock_fs, &mut mock_env);
+        mock_env
+            .expect_new_conda_command()
+            .returning(|_, _| mock_command_echo(CREDENTIAL_TEST_OK));
+
+        let result = test_credential_impl(
+            "FMP".to_string(),
+            "openbb".to_string(),
+            "/mock/install".to_string(),
+            CREDENTIAL_TEST_TIMEOUT,
+            &mock_fs,
+            &mock_env,
+        )
+        .await;
+        assert_eq!(result, Ok(true));
+    }
+
+    #[tokio::test]
+    async fn test_credential_reports_rejected_key() {
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_env = MockEnvSystem::new();
+        mock_credential_test(&mut mock_fs, &mut mock_env);
+        mock_env.expect_new_conda_command().returning(|_, _| {
+            mock_command_echo(&format!(
+                "{CREDENTIAL_TEST_FAILED} Unauthorized: /quote?apikey=fmp_key_123456"
+            ))
+        });
+        mock_env
+            .expect_var()
+            .with(eq("HOME"))
+            .returning(|_| Ok("/mock/home".to_string()));
+        mock_fs.expect_read_to_string().returning(|_| {
+            Ok(
+                r#"{"credentials":{"fmp_api_key":"fmp_key_123456","fred_api_key":null}}"#
+                    .to_string(),
+            )
+        });
+
+        let result = test_credential_impl(
+            "fmp".to_string(),
+            "openbb".to_string(),
+            "/mock/install".to_string(),
+            CREDENTIAL_TEST_TIMEOUT,
+            &mock_fs,
+            &mock_env,
+        )
+        .await;
+        assert_eq!(result, Ok(false));
+    }
+
+    #[tokio::test]
+    async fn test_credential_reports_other_errors_as_failures_to_test() {
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_env = MockEnvSystem::new();
+        mock_credential_test(&mut mock_fs, &mut mock_env);
+        mock_env.expect_new_conda_command().returning(|_, _| {
+            mock_command_echo(&format!(
+                "{CREDENTIAL_TEST_ERROR} ConnectTimeout timed out: /quote?apikey=fmp_key_123456"
+            ))
+        });
+        mock_env
+            .expect_var()
+            .with(eq("HOME"))
+            .returning(|_| Ok("/mock/home".to_string()));
+        mock_fs
+            .expect_read_to_string()
+            .returning(|_| Ok(r#"{"credentials":{"fmp_api_key":"fmp_key_123456"}}"#.to_string()));
+
+        let error = test_credential_impl(
+            "fmp".to_string(),
+            "openbb".to_string(),
+            "/mock/install".to_string(),
+            CREDENTIAL_TEST_TIMEOUT,
+            &mock_fs,
+            &mock_env,
+        )
+        .await
+        .unwrap_err();
+        assert!(error.contains("could not reach the provider"), "{error}");
+        assert!(error.contains("ConnectTimeout"), "{error}");
+        assert!(!error.contains("fmp_key_123456"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_credential_unsupported_provider() {
+        let result = test_credential_impl(
+            "nasdaq".to_string(),
+            "openbb".to_string(),
+            "/mock/install".to_string(),
+            CREDENTIAL_TEST_TIMEOUT,
+            &MockFileSystem::new(),
+            &MockEnvSystem::new(),
+        )
+        .await;
+        assert_eq!(
+            result,
+            Err("Testing credentials for 'nasdaq' is not supported".to_string())
+        );
+    }
+
+    // A hung test is killed with its process group once the timeout passes
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_credential_times_out() {
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_env = MockEnvSystem::new();
+        mock_credential_test(&mut mock_fs, &mut mock_env);
+        mock_env.expect_new_conda_command().returning(|_, _| {
+            // The script path is appended as the shell's $0
+            let mut cmd = std::process::Command::new("sh");
+            cmd.args(["-c", "sleep 30"]);
+            cmd
+        });
+        mock_env
+            .expect_new_command()
+            .with(eq("kill"))
+            .times(1)
+            .returning(|program| std::process::Command::new(program));
+
+        let started = std::time::Instant::now();
+        let error = test_credential_impl(
+            "fmp".to_string(),
+            "openbb".to_string(),
+            "/mock/install".to_string(),
+            std::time::Duration::from_millis(200),
+            &mock_fs,
+            &mock_env,
+        )
+        .await
+        .unwrap_err();
+        assert!(error.contains("timed out"), "{error}");
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+    }
+
     #[tokio::test]
     async fn get_user_credentials_file_exists() {
         let mut mock_fs = MockFileSystem::new();
@@ -336,18 +1207,31 @@ mod tests {
             .expect_exists()
             .with(eq(settings_path.clone()))
             .return_const(false);
+        let tmp_path = platform_dir.join("user_settings.json.tmp");
         mock_fs
             .expect_write()
             .withf(move |path, content| {
-                path == settings_path
+                path == tmp_path
                     && content.contains("credentials")
                     && content.contains("new_key123")
             })
             .returning(|_, _| Ok(()));
+        mock_fs.expect_sync_file().returning(|_| Ok(()));
+        mock_fs
+            .expect_rename()
+            .withf(move |_, to| to == settings_path)
+            .times(1)
+            .returning(|_, _| Ok(()));
 
-        let result = update_user_credentials_impl(test_credentials, &mock_fs, &mock_env).await;
+        let result = update_user_credentials_impl(
+            test_credentials,
+            &mock_fs,
+            &mock_env,
+            &MockBackupKeyStore::new(),
+        )
+        .await;
         assert!(result.is_ok());
-        assert!(result.unwrap());
+        assert_eq!(result.unwrap().unknown, vec!["api_key".to_string()]);
     }
 
     #[tokio::test]
@@ -383,18 +1267,40 @@ mod tests {
             .returning(|_| {
                 Ok(r#"{"credentials":{"api_key":"old_key"},"other_setting":"value"}"#.to_string())
             });
+        let tmp_path = platform_dir.join("user_settings.json.tmp");
         mock_fs
             .expect_write()
             .withf(move |path, content| {
-                path == settings_path
+                path == tmp_path
                     && content.contains("updated_key")
                     && content.contains("other_setting")
             })
             .returning(|_, _| Ok(()));
+        mock_fs.expect_sync_file().returning(|_| Ok(()));
+        // The previous settings are kept as a backup before being replaced
+        mock_fs
+            .expect_copy()
+            .with(
+                eq(settings_path.clone()),
+                eq(platform_dir.join("user_settings.json.bak")),
+            )
+            .times(1)
+            .returning(|_, _| Ok(0));
+        mock_fs
+            .expect_rename()
+            .withf(move |_, to| to == settings_path)
+            .times(1)
+            .returning(|_, _| Ok(()));
 
-        let result = update_user_credentials_impl(test_credentials, &mock_fs, &mock_env).await;
+        let result = update_user_credentials_impl(
+            test_credentials,
+            &mock_fs,
+            &mock_env,
+            &MockBackupKeyStore::new(),
+        )
+        .await;
         assert!(result.is_ok());
-        assert!(result.unwrap());
+        assert_eq!(result.unwrap().unknown, vec!["api_key".to_string()]);
     }
 
     #[tokio::test]
@@ -427,7 +1333,7 @@ mod tests {
         mock_fs
             .expect_write()
             .withf(move |path, content| {
-                path == settings_path
+                path == settings_path.with_extension("json.tmp")
                     && content.contains("credentials")
                     && content.contains("new_key123")
             })
@@ -438,7 +1344,13 @@ mod tests {
                 ))
             });
 
-        let result = update_user_credentials_impl(test_credentials, &mock_fs, &mock_env).await;
+        let result = update_user_credentials_impl(
+            test_credentials,
+            &mock_fs,
+            &mock_env,
+            &MockBackupKeyStore::new(),
+        )
+        .await;
         assert!(result.is_err());
         assert!(
             result
@@ -447,6 +1359,217 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn update_user_credentials_rejects_non_string_value() {
+        // No filesystem expectations: validation must fail before any file access
+        let mock_fs = MockFileSystem::new();
+        let mock_env = MockEnvSystem::new();
+
+        let test_credentials = serde_json::json!({ "fmp_api_key": 12345 });
+
+        let result = update_user_credentials_impl(
+            test_credentials,
+            &mock_fs,
+            &mock_env,
+            &MockBackupKeyStore::new(),
+        )
+        .await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("fmp_api_key"));
+    }
+
+    #[test]
+    fn credential_map_debug_output_is_redacted() {
+        let credentials: CredentialMap =
+            serde_json::from_value(serde_json::json!({ "fmp_api_key": "sk-live-1234567890" }))
+                .unwrap();
+        let printed = format!("{credentials:?}");
+        assert!(printed.contains("fmp_api_key"), "{printed}");
+        assert!(!printed.contains("sk-live-1234567890"), "{printed}");
+        assert_eq!(credentials["fmp_api_key"].expose(), "sk-live-1234567890");
+    }
+
+    #[test]
+    fn validate_credentials_accepts_valid_key_map() {
+        let credentials = serde_json::json!({
+            "fmp_api_key": "abc",
+            "polygon_api_key": "",
+            "my_custom_token": "xyz"
+        });
+
+        let report = validate_credentials(&credentials).unwrap();
+        assert_eq!(
+            report,
+            CredentialKeyReport {
+                recognized: vec!["fmp_api_key".to_string(), "polygon_api_key".to_string()],
+                unknown: vec!["my_custom_token".to_string()],
+            }
+        );
+    }
+
+    // Keychain backed by a shared slot
+    fn in_memory_keys(
+        slot: std::sync::Arc<std::sync::Mutex<Option<String>>>,
+    ) -> MockBackupKeyStore {
+        let mut keys = MockBackupKeyStore::new();
+        let stored = slot.clone();
+        keys.expect_get()
+            .returning(move || Ok(stored.lock().unwrap().clone()));
+        keys.expect_set().returning(move |key| {
+            *slot.lock().unwrap() = Some(key.to_string());
+            Ok(())
+        });
+        keys
+    }
+
+    #[test]
+    fn credential_backups_keep_newest_n() {
+        let mock_fs = InMemoryFS::new();
+        let mock_env = mock_home_env();
+
+        let config = CredentialBackupConfig {
+            enabled: true,
+            keep: 3,
+            encrypt: false,
+        };
+        let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        for i in 0..5 {
+            let credentials = serde_json::json!({ "fmp_api_key": format!("key-{i}") });
+            backup_credentials_impl(
+                &credentials,
+                &config,
+                start + chrono::Duration::minutes(i),
+                &mock_fs,
+                &mock_env,
+                &MockBackupKeyStore::new(),
+            )
+            .unwrap();
+        }
+
+        let backups = list_credential_backups_impl(&mock_fs, &mock_env).unwrap();
+        assert_eq!(
+            backups.iter().map(|b| b.file.as_str()).collect::<Vec<_>>(),
+            vec![
+                "credentials-20231114T221720.000Z.json",
+                "credentials-20231114T221620.000Z.json",
+                "credentials-20231114T221520.000Z.json",
+            ]
+        );
+        assert_eq!(
+            backups[0].created_at.as_deref(),
+            Some("2023-11-14T22:17:20+00:00")
+        );
+        assert_eq!(mock_fs.files.lock().unwrap().len(), 3);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let permissions = mock_fs.permissions.lock().unwrap();
+            let backup_dir = PathBuf::from("/mock/home/.openbb_platform/backups/credentials");
+            for backup in &backups {
+                assert_eq!(
+                    permissions[&backup_dir.join(&backup.file)].mode() & 0o777,
+                    0o600
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn credential_backup_restore_round_trip() {
+        let mock_fs = InMemoryFS::new();
+        let mock_env = mock_home_env();
+        let key_slot = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let keys = in_memory_keys(key_slot.clone());
+
+        set_credential_backup_config_impl(
+            CredentialBackupConfig {
+                enabled: true,
+                keep: 5,
+                encrypt: true,
+            },
+            &mock_fs,
+            &mock_env,
+        )
+        .unwrap();
+
+        let original = serde_json::json!({ "fmp_api_key": "original", "fred_api_key": "fred" });
+        update_user_credentials_impl(original.clone(), &mock_fs, &mock_env, &keys)
+            .await
+            .unwrap();
+        let backup = list_credential_backups_impl(&mock_fs, &mock_env).unwrap()[0].clone();
+        assert!(backup.encrypted);
+
+        // Encrypted backups don't contain the secrets in the clear
+        let backup_path =
+            PathBuf::from("/mock/home/.openbb_platform/backups/credentials").join(&backup.file);
+        assert!(!mock_fs.files.lock().unwrap()[&backup_path].contains("original"));
+        // The key went to the keychain, not next to the backups
+        assert!(key_slot.lock().unwrap().is_some());
+        assert!(!mock_fs.exists(&backup_path.with_file_name(LEGACY_BACKUP_KEY_FILE)));
+
+        update_user_credentials_impl(
+            serde_json::json!({ "fmp_api_key": "overwritten" }),
+            &mock_fs,
+            &mock_env,
+            &keys,
+        )
+        .await
+        .unwrap();
+
+        restore_credential_backup_impl(backup.file.clone(), &mock_fs, &mock_env, &keys)
+            .await
+            .unwrap();
+        let settings = get_user_credentials_impl(&mock_fs, &mock_env)
+            .await
+            .unwrap();
+        assert_eq!(settings["credentials"], original);
+        assert_eq!(settings["preferences"]["credential_backup"]["keep"], 5);
+
+        assert!(
+            restore_credential_backup_impl(
+                "../user_settings.json".to_string(),
+                &mock_fs,
+                &mock_env,
+                &keys
+            )
+            .await
+            .is_err()
+        );
+
+        // Without the key an encrypted backup can't be restored, and no new key is made
+        let empty_keys = in_memory_keys(Default::default());
+        let result =
+            restore_credential_backup_impl(backup.file, &mock_fs, &mock_env, &empty_keys).await;
+        assert!(result.unwrap_err().contains("Backup key is missing"));
+    }
+
+    #[test]
+    fn legacy_backup_key_file_moves_to_keychain() {
+        let mock_fs = InMemoryFS::new();
+        let backup_dir = PathBuf::from("/mock/home/.openbb_platform/backups/credentials");
+        let legacy_key = "ab".repeat(32);
+        mock_fs
+            .write(&backup_dir.join(LEGACY_BACKUP_KEY_FILE), &legacy_key)
+            .unwrap();
+        let key_slot = std::sync::Arc::new(std::sync::Mutex::new(None));
+
+        let key = credential_backup_key(
+            &backup_dir,
+            true,
+            &mock_fs,
+            &in_memory_keys(key_slot.clone()),
+        )
+        .unwrap();
+
+        assert_eq!(key, vec![0xab; 32]);
+        assert_eq!(
+            key_slot.lock().unwrap().as_deref(),
+            Some(legacy_key.as_str())
+        );
+        assert!(!mock_fs.exists(&backup_dir.join(LEGACY_BACKUP_KEY_FILE)));
+    }
+
     #[tokio::test]
     async fn open_credentials_file_file_exists() {
         let mut mock_fs = MockFileSystem::new();
@@ -1,4 +1,5 @@
 use super::helpers::{EnvSystem, RealEnvSystem};
+use crate::utils::maintenance::begin_mutating_command;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::Mutex;
@@ -135,6 +136,8 @@ pub async fn start_jupyter_server_impl<R: tauri::Runtime, E: EnvSystem>(
                 let timestamp = chrono::Utc::now().timestamp_millis();
                 let entry = crate::utils::process_monitor::LogEntry {
                     timestamp,
+                    stream: crate::utils::process_monitor::Stream::Stdout,
+                    level: crate::utils::process_monitor::LogLevel::detect(&line),
                     content: line.clone(),
                     process_id: process_id_clone.clone(),
                 };
@@ -149,6 +152,7 @@ pub async fn start_jupyter_server_impl<R: tauri::Runtime, E: EnvSystem>(
                 let payload = serde_json::json!({
                     "processId": process_id_clone,
                     "output": line,
+                    "stream": crate::utils::process_monitor::Stream::Stdout,
                     "timestamp": timestamp
                 });
                 let _ = app_handle_clone.emit("process-output", payload);
@@ -174,6 +178,8 @@ pub async fn start_jupyter_server_impl<R: tauri::Runtime, E: EnvSystem>(
                 let timestamp = chrono::Utc::now().timestamp_millis();
                 let entry = crate::utils::process_monitor::LogEntry {
                     timestamp,
+                    stream: crate::utils::process_monitor::Stream::Stderr,
+                    level: crate::utils::process_monitor::LogLevel::detect(&line),
                     content: line.clone(),
                     process_id: process_id_clone.clone(),
                 };
@@ -188,6 +194,7 @@ pub async fn start_jupyter_server_impl<R: tauri::Runtime, E: EnvSystem>(
                 let payload = serde_json::json!({
                     "processId": process_id_clone,
                     "output": line,
+                    "stream": crate::utils::process_monitor::Stream::Stderr,
                     "timestamp": timestamp
                 });
                 let _ = app_handle_clone.emit("process-output", payload);
@@ -257,6 +264,7 @@ pub async fn start_jupyter_server<R: tauri::Runtime>(
     directory: String,
     working: String,
 ) -> Result<serde_json::Value, String> {
+    let _operation = begin_mutating_command(&app_handle)?;
     start_jupyter_server_impl(app_handle, environment, directory, working, &RealEnvSystem).await
 }
 
@@ -444,6 +452,8 @@ pub async fn stop_jupyter_server_impl<R: tauri::Runtime, E: EnvSystem>(
 
     let completion_entry = crate::utils::process_monitor::LogEntry {
         timestamp: completion_timestamp,
+        stream: crate::utils::process_monitor::Stream::Stdout,
+        level: crate::utils::process_monitor::LogLevel::Info,
         content: completion_message.clone(),
         process_id: format!("jupyter-{environment}"),
     };
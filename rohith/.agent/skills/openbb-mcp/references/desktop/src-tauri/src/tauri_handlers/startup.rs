@@ -1,13 +1,19 @@
 use crate::tauri_handlers::backends::create_backend_service_impl;
+use crate::tauri_handlers::environments::{
+    invalidate_environment_list, parse_output_progress, run_tracked_command_with_logging,
+};
 use crate::tauri_handlers::helpers::{
-    EnvSystem, FileExtTrait, FileSystem, RealEnvSystem, RealFileSystem,
+    EnvSystem, FileExtTrait, FileSystem, RealEnvSystem, RealFileExtTrait, RealFileSystem,
+    parse_settings_with_backup, with_system_settings_lock, write_with_backup,
 };
+use crate::utils::maintenance::begin_mutating_command;
+use crate::utils::process_monitor::RunningProcesses;
 use once_cell::sync::Lazy;
 use reqwest;
 use serde::Serialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
-use tauri::{Emitter, Window};
+use tauri::{AppHandle, Emitter, Manager, Window};
 
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
@@ -22,6 +28,8 @@ pub struct InstallationState {
     pub is_configuring: bool,
     pub is_complete: bool,
     pub message: String,
+    /// Overall progress from 0.0 to 1.0
+    pub progress: f32,
 }
 
 #[derive(Clone, Serialize)]
@@ -31,6 +39,115 @@ pub struct InstallProgress {
     pub message: String,
 }
 
+/// Tracking id of the Miniforge installer in `RunningProcesses`
+pub const CONDA_INSTALLER_PROCESS_ID: &str = "conda-installer";
+
+/// Tracking id of the `conda env create` run that sets up the openbb environment
+pub const PYTHON_ENV_SETUP_PROCESS_ID: &str = "python-env-setup";
+
+/// Emitted whenever the overall install progress moves, including from installer output
+pub const INSTALLATION_PROGRESS_EVENT: &str = "installation-progress";
+
+/// Payload of `conda-install-progress` events, parsed from the installer's output
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CondaInstallProgress {
+    pub phase: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent: Option<f32>,
+    pub message: String,
+}
+
+/// Parse a line of Miniforge installer output into a progress update.
+/// Package extraction is reported through a tqdm bar ("Extracting : 54%|█████▍ | 20/37"),
+/// the other phases only by their headline.
+pub fn parse_installer_progress_line(line: &str) -> Option<CondaInstallProgress> {
+    static PERCENT: Lazy<regex::Regex> = Lazy::new(|| regex::Regex::new(r"(\d{1,3})%\|").unwrap());
+
+    let line = line.trim();
+    let progress = |phase: &str, percent: Option<f32>| {
+        Some(CondaInstallProgress {
+            phase: phase.to_string(),
+            percent,
+            message: line.to_string(),
+        })
+    };
+
+    if let Some(captures) = PERCENT.captures(line) {
+        let percent = captures[1].parse::<f32>().ok()?.min(100.0);
+        return progress("extract", Some(percent));
+    }
+
+    let lower = line.to_lowercase();
+    if lower.starts_with("unpacking payload") {
+        progress("unpack", None)
+    } else if lower.starts_with("extracting") {
+        progress("extract", None)
+    } else if lower.contains("transaction") {
+        progress("transaction", None)
+    } else if lower.starts_with("installation finished") {
+        progress("finished", Some(100.0))
+    } else {
+        None
+    }
+}
+
+/// Percentage carried by a line of installer output: the Miniforge extraction bar,
+/// conda's download bars or pip's download amounts
+pub fn parse_install_percent(line: &str) -> Option<f32> {
+    parse_installer_progress_line(line)
+        .and_then(|progress| progress.percent)
+        .or_else(|| parse_output_progress(line).and_then(|progress| progress.percent))
+}
+
+/// Map a step's own percentage onto its `start..end` share of the overall progress
+pub fn scale_progress(start: f32, end: f32, percent: f32) -> f32 {
+    start + (end - start) * percent.clamp(0.0, 100.0) / 100.0
+}
+
+/// Overall progress of a step driven by per-package bars. Every package's bar starts
+/// again from 0%, so the step only moves forward to the furthest point reached.
+pub struct StepProgress {
+    start: f32,
+    end: f32,
+    reached: Mutex<f32>,
+}
+
+impl StepProgress {
+    pub fn new(start: f32, end: f32) -> Self {
+        Self {
+            start,
+            end,
+            reached: Mutex::new(start),
+        }
+    }
+
+    /// Overall progress for a bar at `percent`, or None when it would move backwards
+    pub fn advance(&self, percent: f32) -> Option<f32> {
+        let progress = scale_progress(self.start, self.end, percent);
+        let mut reached = self.reached.lock().unwrap();
+        if progress < *reached {
+            return None;
+        }
+        *reached = progress;
+        Some(progress)
+    }
+}
+
+/// Store the overall progress (0.0 to 1.0) and send it as an `installation-progress` event
+pub fn emit_installation_progress(window: &Window, step: &str, progress: f32, message: &str) {
+    let progress = progress.clamp(0.0, 1.0);
+    INSTALLATION_STATE.lock().unwrap().progress = progress;
+
+    let progress_data = InstallProgress {
+        step: step.to_string(),
+        progress,
+        message: message.to_string(),
+    };
+    if let Err(e) = window.emit(INSTALLATION_PROGRESS_EVENT, &progress_data) {
+        log::debug!("Failed to emit installation progress: {e}");
+    }
+}
+
 #[tauri::command]
 pub async fn get_installation_status() -> Result<serde_json::Value, String> {
     let state = INSTALLATION_STATE.lock().unwrap();
@@ -45,6 +162,7 @@ pub async fn get_installation_status() -> Result<serde_json::Value, String> {
         "isInstalling": state.is_installing,
         "isConfiguring": state.is_configuring,
         "isComplete": state.is_complete,
+        "progress": state.progress,
         "message": state.message
     });
 
@@ -60,6 +178,10 @@ pub fn update_installation_state(step: &str, progress: f32, message: &str) {
 
     let mut state = INSTALLATION_STATE.lock().unwrap();
     state.message = message.to_string();
+    // Errors leave the bar where it stopped
+    if step != "error" {
+        state.progress = progress.clamp(0.0, 1.0);
+    }
 
     let step_lower = step.to_lowercase();
     let message_lower = message.to_lowercase();
@@ -189,11 +311,12 @@ fn check_directory_permissions<F: FileSystem>(
     Ok(())
 }
 
-pub async fn install_to_directory_impl<F: FileSystem, E: EnvSystem>(
+pub async fn install_to_directory_impl<F: FileSystem, E: EnvSystem, FE: FileExtTrait>(
     directory: String,
     user_data_directory: String,
     fs: &F,
     env_sys: &E,
+    file_ext: &FE,
 ) -> Result<bool, String> {
     use std::path::Path;
 
@@ -281,7 +404,7 @@ pub async fn install_to_directory_impl<F: FileSystem, E: EnvSystem>(
             user_data_directory.replace("\\", "\\\\")
         ); // Escape backslashes for JSON string
 
-        match fs.write(&user_settings_path, &user_settings) {
+        match write_with_backup(&user_settings_path, &user_settings, fs) {
             Ok(_) => log::debug!("Successfully created user settings file"),
             Err(e) => {
                 let error_msg = format!("Failed to create user settings file: {e}");
@@ -303,14 +426,15 @@ pub async fn install_to_directory_impl<F: FileSystem, E: EnvSystem>(
         };
 
         // Parse JSON
-        let mut settings: serde_json::Value = match serde_json::from_str(&settings_content) {
-            Ok(json) => json,
-            Err(e) => {
-                let error_msg = format!("Failed to parse user settings: {e}");
-                log::debug!("{error_msg}");
-                return Err(error_msg);
-            }
-        };
+        let mut settings =
+            match parse_settings_with_backup(&user_settings_path, &settings_content, fs) {
+                Ok(json) => json,
+                Err(e) => {
+                    let error_msg = format!("Failed to parse user settings: {e}");
+                    log::debug!("{error_msg}");
+                    return Err(error_msg);
+                }
+            };
 
         // Ensure preferences section exists
         if !settings.as_object().unwrap().contains_key("preferences") {
@@ -335,10 +459,11 @@ pub async fn install_to_directory_impl<F: FileSystem, E: EnvSystem>(
                 serde_json::Value::String(user_data_directory.clone());
 
             // Write updated settings back to file
-            match fs.write(
+            match write_with_backup(
                 &user_settings_path,
                 &serde_json::to_string_pretty(&settings)
                     .map_err(|e| format!("Failed to serialize user settings: {e}"))?,
+                fs,
             ) {
                 Ok(_) => log::debug!("Successfully updated data_directory in user settings file"),
                 Err(e) => {
@@ -352,80 +477,393 @@ pub async fn install_to_directory_impl<F: FileSystem, E: EnvSystem>(
         }
     }
 
-    // Update system settings file - read existing content if it exists
-    let mut system_settings = if system_settings_path.exists() {
-        match fs.read_to_string(&system_settings_path) {
-            Ok(content) => match serde_json::from_str::<serde_json::Value>(&content) {
-                Ok(json) => json,
+    with_system_settings_lock(&platform_dir, fs, file_ext, || {
+        // Update system settings file - read existing content if it exists
+        let mut system_settings = if system_settings_path.exists() {
+            match fs.read_to_string(&system_settings_path) {
+                Ok(content) => {
+                    match parse_settings_with_backup(&system_settings_path, &content, fs) {
+                        Ok(json) => json,
+                        Err(_) => {
+                            log::debug!(
+                                "Warning: Could not parse existing system settings, creating new one"
+                            );
+                            serde_json::json!({})
+                        }
+                    }
+                }
                 Err(_) => {
                     log::debug!(
-                        "Warning: Could not parse existing system settings, creating new one"
+                        "Warning: Could not read existing system settings, creating new one"
                     );
                     serde_json::json!({})
                 }
-            },
-            Err(_) => {
-                log::debug!("Warning: Could not read existing system settings, creating new one");
-                serde_json::json!({})
             }
+        } else {
+            log::debug!("Creating new system settings file");
+            serde_json::json!({})
+        };
+
+        // Ensure system_settings is an object
+        if !system_settings.is_object() {
+            system_settings = serde_json::json!({});
         }
-    } else {
-        log::debug!("Creating new system settings file");
-        serde_json::json!({})
-    };
 
-    // Ensure system_settings is an object
-    if !system_settings.is_object() {
-        system_settings = serde_json::json!({});
-    }
+        // Create/update the install_settings section
+        let install_settings = serde_json::json!({
+            "installation_directory": directory,
+            "user_data_directory": user_data_directory,
+            "installation_date": chrono::Local::now().to_rfc3339()
+        });
 
-    // Create/update the install_settings section
-    let install_settings = serde_json::json!({
-        "installation_directory": directory,
-        "user_data_directory": user_data_directory,
-        "installation_date": chrono::Local::now().to_rfc3339()
-    });
+        // Update the install_settings section without affecting other parts
+        if let Some(obj) = system_settings.as_object_mut() {
+            obj.insert("install_settings".to_string(), install_settings);
+        }
+
+        // First, check if the directory exists or needs to be created
+        if !fs.exists(std::path::Path::new(&directory)) {
+            fs.create_dir_all(std::path::Path::new(&directory))
+                .map_err(|e| format!("Failed to create directory: {e}"))?;
+        }
 
-    // Update the install_settings section without affecting other parts
-    if let Some(obj) = system_settings.as_object_mut() {
-        obj.insert("install_settings".to_string(), install_settings);
+        // Write the updated system settings
+        match write_with_backup(
+            &system_settings_path,
+            &serde_json::to_string_pretty(&system_settings)
+                .map_err(|e| format!("Failed to serialize system settings: {e}"))?,
+            fs,
+        ) {
+            Ok(_) => log::debug!("Successfully updated system settings"),
+            Err(e) => {
+                let error_msg = format!("Failed to update system settings: {e}");
+                log::debug!("{error_msg}");
+                return Err(error_msg);
+            }
+        }
+
+        Ok(())
+    })?;
+
+    log::debug!("Installation directories and configuration prepared successfully");
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VolumeType {
+    Local,
+    Network,
+    Removable,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageLocationReport {
+    pub path: String,
+    pub volume_type: VolumeType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+// Command that prints the volume information for `path` on the current OS
+fn build_volume_probe_command<E: EnvSystem>(path: &str, env_sys: &E) -> std::process::Command {
+    match env_sys.consts_os() {
+        "windows" => {
+            let mut command = env_sys.new_command("powershell");
+            command.args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                &format!(
+                    "([System.IO.DriveInfo]::new([System.IO.Path]::GetPathRoot('{}'))).DriveType",
+                    path.replace('\'', "''")
+                ),
+            ]);
+            #[cfg(windows)]
+            command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+            command
+        }
+        "macos" => {
+            let mut command = env_sys.new_command("df");
+            command.args(["-P", path]);
+            command
+        }
+        _ => {
+            let mut command = env_sys.new_command("findmnt");
+            command.args(["-n", "-o", "FSTYPE,SOURCE,TARGET", "--target", path]);
+            command
+        }
     }
+}
 
-    // First, check if the directory exists or needs to be created
-    if !fs.exists(std::path::Path::new(&directory)) {
-        fs.create_dir_all(std::path::Path::new(&directory))
-            .map_err(|e| format!("Failed to create directory: {e}"))?;
+/// Classify the volume from the output of the probe command
+pub fn classify_volume(os: &str, path: &str, probe_output: &str) -> VolumeType {
+    const NETWORK_FS: &[&str] = &[
+        "nfs",
+        "nfs4",
+        "cifs",
+        "smbfs",
+        "smb3",
+        "afpfs",
+        "webdav",
+        "sshfs",
+        "fuse.sshfs",
+        "9p",
+        "davfs",
+    ];
+
+    match os {
+        "windows" => {
+            // UNC paths are always network shares
+            if path.starts_with("\\\\") || path.starts_with("//") {
+                return VolumeType::Network;
+            }
+            match probe_output.trim() {
+                "Fixed" | "Ram" => VolumeType::Local,
+                "Network" => VolumeType::Network,
+                "Removable" | "CDRom" => VolumeType::Removable,
+                _ => VolumeType::Unknown,
+            }
+        }
+        "macos" => {
+            // Last line of `df -P`: filesystem, blocks, used, available, capacity, mount point
+            let Some(line) = probe_output.lines().rfind(|l| !l.trim().is_empty()) else {
+                return VolumeType::Unknown;
+            };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 6 || fields[0] == "Filesystem" {
+                return VolumeType::Unknown;
+            }
+            let (source, mount) = (fields[0], fields[5..].join(" "));
+            if source.starts_with("//") || source.contains(":/") {
+                VolumeType::Network
+            } else if mount.starts_with("/Volumes/") {
+                VolumeType::Removable
+            } else {
+                VolumeType::Local
+            }
+        }
+        _ => {
+            // `findmnt -n -o FSTYPE,SOURCE,TARGET`
+            let fields: Vec<&str> = probe_output.split_whitespace().collect();
+            let (Some(fstype), Some(source)) = (fields.first(), fields.get(1)) else {
+                return VolumeType::Unknown;
+            };
+            let target = fields.get(2..).map(|t| t.join(" ")).unwrap_or_default();
+            if NETWORK_FS.contains(fstype) || source.starts_with("//") || source.contains(":/") {
+                VolumeType::Network
+            } else if target.starts_with("/media/") || target.starts_with("/run/media/") {
+                VolumeType::Removable
+            } else {
+                VolumeType::Local
+            }
+        }
     }
+}
 
-    // Write the updated system settings
-    match fs.write(
-        &system_settings_path,
-        &serde_json::to_string_pretty(&system_settings)
-            .map_err(|e| format!("Failed to serialize system settings: {e}"))?,
-    ) {
-        Ok(_) => log::debug!("Successfully updated system settings"),
+// `path` itself or, for a folder that hasn't been created yet, its closest existing parent
+fn nearest_existing_ancestor<F: FileSystem>(path: &str, fs: &F) -> String {
+    Path::new(path)
+        .ancestors()
+        .find(|ancestor| !ancestor.as_os_str().is_empty() && fs.exists(ancestor))
+        .map_or_else(
+            || path.to_string(),
+            |ancestor| ancestor.to_string_lossy().to_string(),
+        )
+}
+
+/// Warn when `path` lives on a network or removable volume, where conda is slow and fragile.
+/// A folder that doesn't exist yet is judged by the volume of its closest existing parent.
+pub fn check_storage_location_impl<F: FileSystem, E: EnvSystem>(
+    path: &str,
+    fs: &F,
+    env_sys: &E,
+) -> StorageLocationReport {
+    let os = env_sys.consts_os();
+    let probe_path = nearest_existing_ancestor(path, fs);
+    let volume_type = match build_volume_probe_command(&probe_path, env_sys).output() {
+        Ok(output) if output.status.success() => {
+            classify_volume(os, &probe_path, &String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            log::debug!(
+                "Volume probe for {probe_path} failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            classify_volume(os, &probe_path, "")
+        }
         Err(e) => {
-            let error_msg = format!("Failed to update system settings: {e}");
-            log::debug!("{error_msg}");
-            return Err(error_msg);
+            log::debug!("Could not run volume probe for {probe_path}: {e}");
+            classify_volume(os, &probe_path, "")
         }
+    };
+
+    let warning = match volume_type {
+        VolumeType::Network => Some(format!(
+            "{path} is on a network drive. Conda environments on network storage are slow and may break; choose a local folder instead."
+        )),
+        VolumeType::Removable => Some(format!(
+            "{path} is on a removable drive. Environments will stop working when the drive is disconnected; choose a local folder instead."
+        )),
+        VolumeType::Local | VolumeType::Unknown => None,
+    };
+
+    if let Some(warning) = &warning {
+        log::warn!("{warning}");
     }
 
-    log::debug!("Installation directories and configuration prepared successfully");
-    Ok(true)
+    StorageLocationReport {
+        path: path.to_string(),
+        volume_type,
+        warning,
+    }
 }
 
+/// Check the given path, or the platform settings directory and install directory
+#[tauri::command]
+pub async fn check_storage_location(
+    path: Option<String>,
+) -> Result<Vec<StorageLocationReport>, String> {
+    use crate::tauri_handlers::helpers::get_installation_directory_impl;
+
+    let paths = match path {
+        Some(path) => vec![path],
+        None => {
+            let home_dir = RealEnvSystem
+                .var("HOME")
+                .or_else(|_| RealEnvSystem.var("USERPROFILE"))
+                .map_err(|e| format!("Could not determine home directory: {e}"))?;
+            let mut paths = vec![
+                Path::new(&home_dir)
+                    .join(".openbb_platform")
+                    .to_string_lossy()
+                    .to_string(),
+            ];
+            if let Ok(install_dir) =
+                get_installation_directory_impl(&RealFileSystem, &RealEnvSystem)
+            {
+                paths.push(install_dir);
+            }
+            paths
+        }
+    };
+
+    tokio::task::spawn_blocking(move || {
+        paths
+            .iter()
+            .map(|path| check_storage_location_impl(path, &RealFileSystem, &RealEnvSystem))
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Storage location check failed: {e}"))
+}
+
+const MIRROR_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+// A pip index ("…/simple") is probed directly; a conda channel through its noarch repodata
+fn mirror_probe_url(mirror: &str) -> String {
+    let mirror = mirror.trim_end_matches('/');
+    if mirror.ends_with(".json") || mirror.contains("/simple") {
+        mirror.to_string()
+    } else {
+        format!("{mirror}/noarch/repodata.json")
+    }
+}
+
+// Proxies come from the usual environment variables; reading them through EnvSystem
+// keeps the choice visible to tests
+fn mirror_probe_client<E: EnvSystem>(env_sys: &E) -> Result<reqwest::Client, String> {
+    let var = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| env_sys.var(name).ok().filter(|v| !v.trim().is_empty()))
+    };
+    let no_proxy = var(&["NO_PROXY", "no_proxy"]).and_then(|v| reqwest::NoProxy::from_string(&v));
+
+    let mut builder = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(MIRROR_PROBE_TIMEOUT)
+        .user_agent("ODP-Mirror-Benchmark");
+    for (scheme, names) in [
+        ("http", ["HTTP_PROXY", "http_proxy"]),
+        ("https", ["HTTPS_PROXY", "https_proxy"]),
+    ] {
+        if let Some(proxy_url) = var(&names) {
+            let proxy = if scheme == "http" {
+                reqwest::Proxy::http(&proxy_url)
+            } else {
+                reqwest::Proxy::https(&proxy_url)
+            }
+            .map_err(|e| format!("Invalid {scheme} proxy '{proxy_url}': {e}"))?;
+            builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+        }
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {e}"))
+}
+
+/// Time a request to each mirror, returning (mirror, latency in ms) with the fastest
+/// first. Unreachable or failing mirrors report `None` and sort last.
+pub async fn benchmark_mirrors_impl<E: EnvSystem>(
+    mirrors: Vec<String>,
+    env_sys: &E,
+) -> Result<Vec<(String, Option<u128>)>, String> {
+    let client = mirror_probe_client(env_sys)?;
+
+    let probes = mirrors.into_iter().map(|mirror| {
+        let client = client.clone();
+        async move {
+            let url = mirror_probe_url(&mirror);
+            let started = std::time::Instant::now();
+            // Only the time to the response headers matters; the body is never read
+            let latency = match client.get(&url).send().await {
+                Ok(response) if response.status().is_success() => {
+                    Some(started.elapsed().as_millis())
+                }
+                Ok(response) => {
+                    log::debug!("Mirror {url} answered with {}", response.status());
+                    None
+                }
+                Err(e) => {
+                    log::debug!("Mirror {url} is unreachable: {e}");
+                    None
+                }
+            };
+            (mirror, latency)
+        }
+    });
+
+    let mut results = futures::future::join_all(probes).await;
+    results.sort_by_key(|(_, latency)| latency.unwrap_or(u128::MAX));
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn benchmark_mirrors(
+    mirrors: Vec<String>,
+) -> Result<Vec<(String, Option<u128>)>, String> {
+    benchmark_mirrors_impl(mirrors, &RealEnvSystem).await
+}
+
+/// Set up the install directories. The setup page runs `check_storage_location` on the
+/// directory first, so the user can pick a local folder before anything is written.
 #[tauri::command]
 pub async fn install_to_directory(
     directory: String,
     user_data_directory: String,
-) -> Result<bool, String> {
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let _operation = begin_mutating_command(&app_handle)?;
+
     // Use the real file system implementation
     install_to_directory_impl(
         directory,
         user_data_directory,
         &RealFileSystem,
         &RealEnvSystem,
+        &RealFileExtTrait,
     )
     .await
 }
@@ -434,11 +872,18 @@ pub async fn install_to_directory(
 static INSTALLATION_IN_PROGRESS: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(false));
 
 #[tauri::command]
-pub async fn install_conda(directory: String, window: Window) -> Result<bool, String> {
+pub async fn install_conda(
+    directory: String,
+    installer_path: Option<String>,
+    installer_url: Option<String>,
+    window: Window,
+) -> Result<bool, String> {
     use std::fs;
     use std::path::Path;
     use std::process::Command;
 
+    let _operation = begin_mutating_command(window.app_handle())?;
+
     // Prevent multiple simultaneous installations
     {
         let mut in_progress = INSTALLATION_IN_PROGRESS.lock().unwrap();
@@ -466,6 +911,7 @@ pub async fn install_conda(directory: String, window: Window) -> Result<bool, St
         state.is_downloading = false;
         state.is_configuring = false;
         state.is_complete = false;
+        state.progress = 0.0;
 
         // Send explicit error event to UI
         let progress_data = InstallProgress {
@@ -515,9 +961,24 @@ pub async fn install_conda(directory: String, window: Window) -> Result<bool, St
         };
 
         let _ = window.emit("install-progress", &progress_data);
+        emit_installation_progress(&window, step, progress, message);
         log::debug!("[{}] ({:.1}%) {}", step, progress * 100.0, message);
     };
 
+    // INSTALLER SOURCE - checked before touching an existing installation
+    let installer_source = match select_installer_source(
+        installer_path.as_deref(),
+        installer_url.as_deref(),
+        &RealFileSystem,
+    ) {
+        Ok(source) => source,
+        Err(e) => {
+            release_guard();
+            return Err(report_fatal_error(&e));
+        }
+    };
+    let use_local_installer = matches!(installer_source, InstallerSource::Local(_));
+
     // DIRECTORY SETUP
     let install_path = Path::new(&directory);
     let conda_dir = install_path.join("conda");
@@ -591,139 +1052,47 @@ pub async fn install_conda(directory: String, window: Window) -> Result<bool, St
         }
     };
 
-    // DETERMINE URL
-    let installer_url = match fetch_miniforge_installer_url(arch.as_str()).await {
-        Ok(url) => url,
-        Err(e) => {
-            release_guard();
-            return Err(report_fatal_error(&format!(
-                "Failed to fetch Miniforge installer URL: {e}"
-            )));
-        }
-    };
-
-    report_progress(
-        "download",
-        0.2,
-        &format!("Using installer: {installer_url}"),
-    );
-
-    // TEMPORARY DIRECTORY SETUP
-    let temp_dir = std::env::temp_dir().join("openbb_installer");
-    if !temp_dir.exists() {
-        match fs::create_dir_all(&temp_dir) {
-            Ok(_) => {}
-            Err(e) => {
-                release_guard();
-                return Err(report_fatal_error(&format!(
-                    "Failed to create temp directory: {e}"
-                )));
-            }
+    // DETERMINE INSTALLER
+    let installer_path = match installer_source {
+        InstallerSource::Local(path) => {
+            report_progress(
+                "download",
+                0.25,
+                &format!("Using local installer: {}", path.display()),
+            );
+            path
         }
-    }
-
-    // INSTALLER PATH
-    let installer_path = if std::env::consts::OS == "windows" {
-        temp_dir.join("miniforge_installer.exe")
-    } else {
-        temp_dir.join("miniforge_installer.sh")
-    };
-
-    // Remove existing installer if it exists
-    if installer_path.exists()
-        && let Err(e) = fs::remove_file(&installer_path)
-    {
-        log::debug!("Warning: Could not remove existing installer: {e}");
-        // Non-fatal, continue
-    }
-
-    // DOWNLOAD THE INSTALLER - explicitly set download phase
-    report_progress("download", 0.25, "Downloading Miniforge installer");
-
-    // For Unix systems
-    if std::env::consts::OS != "windows" {
-        let curl_args = [
-            "--http1.1",
-            "-L",
-            "-o",
-            &installer_path.to_string_lossy(),
-            "--fail",
-            "--retry",
-            "3",
-            "--connect-timeout",
-            "30",
-            "--silent",
-            "--show-error",
-            &installer_url,
-        ];
+        source => {
+            let installer_url = match source {
+                InstallerSource::Url(url) => url,
+                _ => match fetch_miniforge_installer_url(arch.as_str()).await {
+                    Ok(url) => url,
+                    Err(e) => {
+                        release_guard();
+                        return Err(report_fatal_error(&format!(
+                            "Failed to fetch Miniforge installer URL: {e}"
+                        )));
+                    }
+                },
+            };
 
-        let curl_output = Command::new("curl").args(curl_args).output();
+            report_progress(
+                "download",
+                0.2,
+                &format!("Using installer: {installer_url}"),
+            );
 
-        match curl_output {
-            Ok(output) => {
-                if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
+            // DOWNLOAD THE INSTALLER - explicitly set download phase
+            report_progress("download", 0.25, "Downloading Miniforge installer");
+            match download_installer(&installer_url).await {
+                Ok(path) => path,
+                Err(e) => {
                     release_guard();
-                    return Err(report_fatal_error(&format!("Download failed: {stderr}")));
+                    return Err(report_fatal_error(&e));
                 }
             }
-            Err(e) => {
-                release_guard();
-                return Err(report_fatal_error(&format!("Failed to execute curl: {e}")));
-            }
-        }
-    } else {
-        // For Windows, use reqwest to download
-        let response = match reqwest::get(&installer_url).await {
-            Ok(res) => res,
-            Err(e) => {
-                release_guard();
-                return Err(report_fatal_error(&format!("Download failed: {e}")));
-            }
-        };
-
-        if !response.status().is_success() {
-            release_guard();
-            return Err(report_fatal_error(&format!(
-                "Download failed with status: {}",
-                response.status()
-            )));
-        }
-
-        let content = match response.bytes().await {
-            Ok(bytes) => bytes,
-            Err(e) => {
-                release_guard();
-                return Err(report_fatal_error(&format!(
-                    "Failed to read download content: {e}"
-                )));
-            }
-        };
-
-        let mut dest = match fs::File::create(&installer_path) {
-            Ok(file) => file,
-            Err(e) => {
-                release_guard();
-                return Err(report_fatal_error(&format!(
-                    "Failed to create installer file: {e}"
-                )));
-            }
-        };
-
-        if let Err(e) = std::io::copy(&mut content.as_ref(), &mut dest) {
-            release_guard();
-            return Err(report_fatal_error(&format!(
-                "Failed to write to installer file: {e}"
-            )));
         }
-    }
-
-    if !installer_path.exists() {
-        release_guard();
-        return Err(report_fatal_error(
-            "Installer file not found after download",
-        ));
-    }
+    };
 
     // Check file size
     let file_size = match fs::metadata(&installer_path) {
@@ -740,36 +1109,24 @@ pub async fn install_conda(directory: String, window: Window) -> Result<bool, St
     if file_size < 10_000_000 {
         release_guard();
         return Err(report_fatal_error(&format!(
-            "Downloaded file is too small ({file_size} bytes). The download may be incomplete."
+            "Installer file is too small ({file_size} bytes). The download may be incomplete."
         )));
     }
 
     report_progress("install", 0.5, "Download complete. Preparing installation");
 
-    // MAKE INSTALLER EXECUTABLE (Unix only)
-    #[cfg(not(target_os = "windows"))]
-    {
-        match Command::new("chmod")
-            .args(["+x", &installer_path.to_string_lossy()])
-            .status()
-        {
-            Ok(status) if status.success() => {
-                log::debug!("Successfully made installer executable");
-            }
-            Ok(status) => {
-                release_guard();
-                return Err(report_fatal_error(&format!(
-                    "Failed to make installer executable. chmod exited with status: {status}"
-                )));
-            }
-            Err(e) => {
-                release_guard();
-                return Err(report_fatal_error(&format!("Failed to execute chmod: {e}")));
-            }
-        }
-    } // RUN THE INSTALLER
-    report_progress("install", 0.55, "Running Miniforge installer");
-    let install_result = if std::env::consts::OS == "windows" {
+    // RUN THE INSTALLER
+    if std::env::consts::OS == "windows" {
+        // The silent (/S) NSIS installer prints nothing, so there are no progress lines to relay
+        report_progress(
+            "install",
+            0.55,
+            "Running Miniforge installer. It reports no progress on Windows and can take several minutes",
+        );
+    } else {
+        report_progress("install", 0.55, "Running Miniforge installer");
+    }
+    let installer_command = if std::env::consts::OS == "windows" {
         let mut cmd = Command::new("cmd");
 
         #[cfg(windows)]
@@ -807,32 +1164,56 @@ pub async fn install_conda(directory: String, window: Window) -> Result<bool, St
         );
 
         // Run the installer via cmd start
-        cmd.output()
+        cmd
     } else {
         // Unix: Run with bash, using -u flag to BYPASS MD5 VERIFICATION
-        Command::new("bash")
-            .arg(&installer_path)
-            .args([
-                "-b", // batch mode
-                "-u", // bypass MD5 verification
-                "-p",
-                &conda_dir.to_string_lossy(),
-                "-f", // force installation
-            ])
-            .output()
+        let mut cmd = Command::new("bash");
+        cmd.arg(&installer_path).args([
+            "-b", // batch mode
+            "-u", // bypass MD5 verification
+            "-p",
+            &conda_dir.to_string_lossy(),
+            "-f", // force installation
+        ]);
+        cmd
     };
 
+    // Track the installer so abort_installation can kill it, and relay its progress
+    let progress_window = window.clone();
+    // The installer runs between the 55% and 90% marks
+    let install_progress = StepProgress::new(0.55, 0.9);
+    let install_result = run_tracked_command_with_logging(
+        installer_command,
+        CONDA_INSTALLER_PROCESS_ID,
+        window.app_handle(),
+        move |line| {
+            if let Some(progress) = parse_installer_progress_line(line) {
+                if let Some(overall) = progress
+                    .percent
+                    .and_then(|percent| install_progress.advance(percent))
+                {
+                    emit_installation_progress(
+                        &progress_window,
+                        "install",
+                        overall,
+                        &progress.message,
+                    );
+                }
+                let _ = progress_window.emit("conda-install-progress", &progress);
+            }
+        },
+    );
+
     // Check if installation succeeded
     match install_result {
-        Ok(output) => {
-            if !output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let stderr = String::from_utf8_lossy(&output.stderr);
-
+        Ok((status, stdout, stderr)) => {
+            if !status.success() {
                 release_guard();
                 return Err(report_fatal_error(&format!(
                     "Conda installation failed:\nExit code: {}\nStdout: {}\nStderr: {}",
-                    output.status, stdout, stderr
+                    status,
+                    stdout.join("\n"),
+                    stderr.join("\n")
                 )));
             }
         }
@@ -875,8 +1256,8 @@ remote_max_retries: 5
         }
     }
 
-    // CLEANUP
-    if let Err(e) = fs::remove_file(&installer_path) {
+    // CLEANUP - a local installer belongs to the user, so only remove downloads
+    if !use_local_installer && let Err(e) = fs::remove_file(&installer_path) {
         log::debug!("Warning: Could not remove installer file: {e}");
         // Non-fatal, continue
     }
@@ -896,7 +1277,15 @@ remote_max_retries: 5
         )));
     }
 
-    report_progress("complete", 1.0, "Conda installation completed successfully");
+    let complete_message = match crate::tauri_handlers::environments::conda_volume_warning(
+        &conda_dir,
+        &RealFileSystem,
+        &RealEnvSystem,
+    ) {
+        Some(warning) => format!("Conda installation completed successfully. {warning}"),
+        None => "Conda installation completed successfully".to_string(),
+    };
+    report_progress("complete", 1.0, &complete_message);
 
     // Release the installation lock
     release_guard();
@@ -1095,11 +1484,184 @@ pub async fn abort_installation_impl<F: FileSystem, E: EnvSystem>(
 }
 
 #[tauri::command]
-pub async fn abort_installation(directory: String) -> Result<(), String> {
+pub async fn abort_installation(directory: String, app_handle: AppHandle) -> Result<(), String> {
+    // Kill the installer directly when we launched it; the pattern-based cleanup covers the rest
+    if let Some(processes) = app_handle.try_state::<RunningProcesses>() {
+        match processes.kill_process(CONDA_INSTALLER_PROCESS_ID) {
+            Ok(true) => log::debug!("Killed running conda installer"),
+            Ok(false) => {}
+            Err(e) => log::warn!("Failed to kill conda installer: {e}"),
+        }
+        match processes.kill_process(PYTHON_ENV_SETUP_PROCESS_ID) {
+            Ok(true) => log::debug!("Killed running environment setup"),
+            Ok(false) => {}
+            Err(e) => log::warn!("Failed to kill environment setup: {e}"),
+        }
+    }
+
     // Use the real file system and environment system implementations
     abort_installation_impl(directory, &RealFileSystem, &RealEnvSystem).await
 }
 
+/// Where `install_conda` gets the Miniforge installer from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallerSource {
+    /// A pre-downloaded installer on disk, used without any network access
+    Local(PathBuf),
+    /// A mirror serving the installer
+    Url(String),
+    /// The latest Miniforge release on GitHub
+    Default,
+}
+
+/// Pick the installer source, preferring a local installer over a custom URL
+pub fn select_installer_source<F: FileSystem>(
+    installer_path: Option<&str>,
+    installer_url: Option<&str>,
+    fs: &F,
+) -> Result<InstallerSource, String> {
+    let installer_path = installer_path.map(str::trim).filter(|p| !p.is_empty());
+    let installer_url = installer_url.map(str::trim).filter(|u| !u.is_empty());
+
+    if let Some(path) = installer_path {
+        let path = PathBuf::from(path);
+        if !fs.exists(&path) {
+            return Err(format!("Installer not found: {}", path.display()));
+        }
+        let metadata = fs
+            .metadata(&path)
+            .map_err(|e| format!("Failed to read installer {}: {e}", path.display()))?;
+        if !metadata.is_file() {
+            return Err(format!("Installer is not a file: {}", path.display()));
+        }
+        if !is_installer_file(&path) {
+            return Err(format!(
+                "Installer must be a .{INSTALLER_EXTENSION} file: {}",
+                path.display()
+            ));
+        }
+        return Ok(InstallerSource::Local(path));
+    }
+
+    if let Some(url) = installer_url {
+        // The installer runs unverified, so it must not be open to tampering in transit
+        if !url.starts_with("https://") {
+            return Err(format!("Installer URL must use https: {url}"));
+        }
+        return Ok(InstallerSource::Url(url.to_string()));
+    }
+
+    Ok(InstallerSource::Default)
+}
+
+// Miniforge ships as an .exe on Windows and a shell script run through bash elsewhere,
+// so a script needs no execute permission
+const INSTALLER_EXTENSION: &str = if cfg!(windows) { "exe" } else { "sh" };
+
+fn is_installer_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(INSTALLER_EXTENSION))
+}
+
+/// Download the installer into the temp directory and make it executable
+async fn download_installer(installer_url: &str) -> Result<PathBuf, String> {
+    use std::fs;
+    use std::process::Command;
+
+    // TEMPORARY DIRECTORY SETUP
+    let temp_dir = std::env::temp_dir().join("openbb_installer");
+    if !temp_dir.exists() {
+        fs::create_dir_all(&temp_dir)
+            .map_err(|e| format!("Failed to create temp directory: {e}"))?;
+    }
+
+    // INSTALLER PATH
+    let installer_path = if std::env::consts::OS == "windows" {
+        temp_dir.join("miniforge_installer.exe")
+    } else {
+        temp_dir.join("miniforge_installer.sh")
+    };
+
+    // Remove existing installer if it exists
+    if installer_path.exists()
+        && let Err(e) = fs::remove_file(&installer_path)
+    {
+        log::debug!("Warning: Could not remove existing installer: {e}");
+        // Non-fatal, continue
+    }
+
+    // For Unix systems
+    if std::env::consts::OS != "windows" {
+        let curl_args = [
+            "--http1.1",
+            "-L",
+            "-o",
+            &installer_path.to_string_lossy(),
+            "--fail",
+            "--retry",
+            "3",
+            "--connect-timeout",
+            "30",
+            "--silent",
+            "--show-error",
+            installer_url,
+        ];
+
+        let output = Command::new("curl")
+            .args(curl_args)
+            .output()
+            .map_err(|e| format!("Failed to execute curl: {e}"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Download failed: {stderr}"));
+        }
+    } else {
+        // For Windows, use reqwest to download
+        let response = reqwest::get(installer_url)
+            .await
+            .map_err(|e| format!("Download failed: {e}"))?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "Download failed with status: {}",
+                response.status()
+            ));
+        }
+
+        let content = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to read download content: {e}"))?;
+
+        let mut dest = fs::File::create(&installer_path)
+            .map_err(|e| format!("Failed to create installer file: {e}"))?;
+
+        std::io::copy(&mut content.as_ref(), &mut dest)
+            .map_err(|e| format!("Failed to write to installer file: {e}"))?;
+    }
+
+    if !installer_path.exists() {
+        return Err("Installer file not found after download".to_string());
+    }
+
+    // MAKE INSTALLER EXECUTABLE (Unix only)
+    #[cfg(not(target_os = "windows"))]
+    {
+        let status = Command::new("chmod")
+            .args(["+x", &installer_path.to_string_lossy()])
+            .status()
+            .map_err(|e| format!("Failed to execute chmod: {e}"))?;
+        if !status.success() {
+            return Err(format!(
+                "Failed to make installer executable. chmod exited with status: {status}"
+            ));
+        }
+        log::debug!("Successfully made installer executable");
+    }
+
+    Ok(installer_path)
+}
+
 async fn fetch_miniforge_installer_url(arch: &str) -> Result<String, String> {
     // Map Rust's architecture names to the ones used by Miniforge
     let miniforge_arch = match arch {
@@ -1230,15 +1792,19 @@ pub async fn setup_python_environment(
     python_version: String,
     window: Window,
 ) -> Result<bool, String> {
+    let app_handle = window.app_handle().clone();
+    let _operation = begin_mutating_command(&app_handle)?;
     // Delegate to the actual implementation
-    setup_python_environment_impl(
+    let result = setup_python_environment_impl(
         directory,
         python_version,
         window,
         &RealFileSystem,
         &RealEnvSystem,
     )
-    .await
+    .await;
+    invalidate_environment_list(&app_handle);
+    result
 }
 
 // Split the large function into a separate implementation
@@ -1265,6 +1831,7 @@ async fn setup_python_environment_impl<F: FileSystem, E: EnvSystem>(
             Ok(_) => (),
             Err(e) => log::debug!("Failed to emit progress event: {e}"),
         }
+        emit_installation_progress(&window, step, progress, message);
         log::debug!("[{}] ({:.1}%) {}", step, progress * 100.0, message);
     };
 
@@ -1287,7 +1854,8 @@ async fn setup_python_environment_impl<F: FileSystem, E: EnvSystem>(
 
     // Generate YAML file for the environment and create it
     let yaml_path = generate_environment_yaml(&python_version, fs, env_sys).await?;
-    create_environment_from_yaml(&conda_exe, &yaml_path, &report_progress, env_sys).await?;
+    create_environment_from_yaml(&conda_exe, &yaml_path, &window, &report_progress, env_sys)
+        .await?;
 
     // Update OpenBB settings
     if let Err(e) = crate::tauri_handlers::helpers::update_openbb_settings_impl(
@@ -1396,6 +1964,7 @@ where
 async fn create_environment_from_yaml<F, E: EnvSystem>(
     conda_exe: &Path,
     yaml_path: &Path,
+    window: &Window,
     report_progress: &F,
     env_sys: &E,
 ) -> Result<(), String>
@@ -1408,18 +1977,31 @@ where
     let conda_path = conda_exe.parent().unwrap().parent().unwrap();
 
     let mut cmd = env_sys.new_conda_command(conda_exe, conda_path);
-    let env_create_output = cmd
-        .args(["env", "create", "-f", &yaml_path.to_string_lossy(), "-y"])
-        .output()
-        .map_err(|e| format!("Failed to create environment from YAML: {e}"))?;
-
-    if !env_create_output.status.success() {
-        let stderr = String::from_utf8_lossy(&env_create_output.stderr);
-        let stdout = String::from_utf8_lossy(&env_create_output.stdout);
+    cmd.args(["env", "create", "-f", &yaml_path.to_string_lossy(), "-y"]);
+
+    // Conda and pip download bars move the overall progress between 80% and 95%
+    let progress_window = window.clone();
+    let setup_progress = StepProgress::new(0.8, 0.95);
+    let (status, stdout, stderr) = run_tracked_command_with_logging(
+        cmd,
+        PYTHON_ENV_SETUP_PROCESS_ID,
+        window.app_handle(),
+        move |line| {
+            if let Some(overall) =
+                parse_install_percent(line).and_then(|percent| setup_progress.advance(percent))
+            {
+                emit_installation_progress(&progress_window, "config", overall, line);
+            }
+        },
+    )
+    .map_err(|e| format!("Failed to create environment from YAML: {e}"))?;
 
+    if !status.success() {
         return Err(format!(
             "Failed to create environment from YAML:\nExit code: {}\nStdout: {}\nStderr: {}",
-            env_create_output.status, stdout, stderr
+            status,
+            stdout.join("\n"),
+            stderr.join("\n")
         ));
     }
 
@@ -1429,7 +2011,8 @@ where
 /// Create default backend services (OpenBB API and MCP)
 /// This should only be called after a successful full installation
 #[tauri::command]
-pub async fn create_default_backend_services() -> Result<(), String> {
+pub async fn create_default_backend_services(app_handle: AppHandle) -> Result<(), String> {
+    let _operation = begin_mutating_command(&app_handle)?;
     use crate::tauri_handlers::helpers::{RealEnvSystem, RealFileExtTrait, RealFileSystem};
     create_default_backend_services_impl(&RealFileSystem, &RealEnvSystem, &RealFileExtTrait).await
 }
@@ -1447,6 +2030,9 @@ async fn create_default_backend_services_impl<F: FileSystem, E: EnvSystem, FE: F
         env_vars: None,
         environment: "openbb".to_string(),
         auto_start: false,
+        auto_port: false,
+        extra_args: Vec::new(),
+        depends_on: Vec::new(),
         working_directory: None,
         status: "stopped".to_string(),
         pid: None,
@@ -1455,6 +2041,9 @@ async fn create_default_backend_services_impl<F: FileSystem, E: EnvSystem, FE: F
         host: None,
         port: None,
         url: None,
+        health_check_url: None,
+        health_check_interval_secs: None,
+        health: None,
     };
     let _ = create_backend_service_impl(backend, fs, env_sys, file_ext);
 
@@ -1466,6 +2055,9 @@ async fn create_default_backend_services_impl<F: FileSystem, E: EnvSystem, FE: F
         env_vars: None,
         environment: "openbb".to_string(),
         auto_start: false,
+        auto_port: false,
+        extra_args: Vec::new(),
+        depends_on: Vec::new(),
         working_directory: None,
         status: "stopped".to_string(),
         pid: None,
@@ -1474,6 +2066,9 @@ async fn create_default_backend_services_impl<F: FileSystem, E: EnvSystem, FE: F
         host: None,
         port: None,
         url: None,
+        health_check_url: None,
+        health_check_interval_secs: None,
+        health: None,
     };
     let _ = create_backend_service_impl(mcp_backend, fs, env_sys, file_ext);
 
@@ -1538,6 +2133,358 @@ mod tests {
     use crate::tauri_handlers::helpers::{MockEnvSystem, MockFileExtTrait, MockFileSystem};
     use std::path::PathBuf;
 
+    // Minimal HTTP server that answers every request with `status` after `delay`
+    async fn delayed_endpoint(delay: std::time::Duration, status: u16) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = [0u8; 1024];
+                    let _ = stream.read(&mut request).await;
+                    tokio::time::sleep(delay).await;
+                    let response = format!(
+                        "HTTP/1.1 {status} OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        format!("http://{address}")
+    }
+
+    #[tokio::test]
+    async fn test_benchmark_mirrors_sorts_by_latency() {
+        let mut mock_env = MockEnvSystem::new();
+        mock_env
+            .expect_var()
+            .returning(|_| Err(std::env::VarError::NotPresent));
+
+        let slow = delayed_endpoint(std::time::Duration::from_millis(400), 200).await;
+        let fast = delayed_endpoint(std::time::Duration::from_millis(20), 200).await;
+        let broken = delayed_endpoint(std::time::Duration::ZERO, 404).await;
+        // Bind then drop to get a port nothing listens on
+        let closed = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+
+        let results = benchmark_mirrors_impl(
+            vec![slow.clone(), closed.clone(), fast.clone(), broken.clone()],
+            &mock_env,
+        )
+        .await
+        .unwrap();
+
+        let order: Vec<&str> = results.iter().map(|(m, _)| m.as_str()).collect();
+        assert_eq!(&order[..2], &[fast.as_str(), slow.as_str()]);
+        assert!(results[1].1.unwrap() >= 400);
+        assert!(results[0].1.unwrap() < results[1].1.unwrap());
+        assert!(results[2..].iter().all(|(_, latency)| latency.is_none()));
+    }
+
+    #[test]
+    fn test_mirror_probe_url() {
+        assert_eq!(
+            mirror_probe_url("https://conda.anaconda.org/conda-forge/"),
+            "https://conda.anaconda.org/conda-forge/noarch/repodata.json"
+        );
+        assert_eq!(
+            mirror_probe_url("https://pypi.org/simple"),
+            "https://pypi.org/simple"
+        );
+    }
+
+    #[test]
+    fn test_parse_installer_progress_line() {
+        let extract = parse_installer_progress_line(
+            "Extracting : openssl-3.3.2-hb9d3cd8_0.conda:  54%|█████▍    | 20/37 [00:01<00:00, 14.28it/s]",
+        )
+        .unwrap();
+        assert_eq!(extract.phase, "extract");
+        assert_eq!(extract.percent, Some(54.0));
+
+        assert_eq!(
+            parse_installer_progress_line("100%|██████████| 37/37 [00:02<00:00, 15.01it/s]")
+                .unwrap()
+                .percent,
+            Some(100.0)
+        );
+        assert_eq!(
+            parse_installer_progress_line("Unpacking payload ...")
+                .unwrap()
+                .phase,
+            "unpack"
+        );
+        assert_eq!(
+            parse_installer_progress_line("Executing transaction: done")
+                .unwrap()
+                .phase,
+            "transaction"
+        );
+
+        let finished = parse_installer_progress_line("installation finished.").unwrap();
+        assert_eq!(finished.phase, "finished");
+        assert_eq!(finished.percent, Some(100.0));
+
+        assert_eq!(
+            parse_installer_progress_line("PREFIX=/opt/openbb/conda"),
+            None
+        );
+        assert_eq!(parse_installer_progress_line(""), None);
+    }
+
+    #[test]
+    fn test_parse_install_percent_from_installer_output() {
+        let samples = [
+            (
+                "Extracting : openssl-3.3.2-hb9d3cd8_0.conda:  54%|█████▍    | 20/37 [00:01<00:00, 14.28it/s]",
+                Some(54.0),
+            ),
+            (
+                "Extracting : 100%|██████████| 37/37 [00:02<00:00, 15.02it/s]",
+                Some(100.0),
+            ),
+            ("installation finished.", Some(100.0)),
+            (
+                "   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ 12.0/16.0 MB 5.1 MB/s eta 0:00:01",
+                Some(75.0),
+            ),
+            ("Collecting openbb-core==1.4.0", None),
+            ("PREFIX=/opt/openbb/conda", None),
+            ("", None),
+        ];
+        for (line, expected) in samples {
+            assert_eq!(parse_install_percent(line), expected, "line: {line:?}");
+        }
+    }
+
+    #[test]
+    fn test_select_installer_source() {
+        let dir = std::env::temp_dir().join(format!("openbb_installer_src_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let installer = dir.join(if cfg!(windows) {
+            "Miniforge3-Windows-x86_64.exe"
+        } else {
+            "Miniforge3-Linux-x86_64.sh"
+        });
+        std::fs::write(&installer, "#!/bin/sh\n").unwrap();
+        let installer_str = installer.to_string_lossy().to_string();
+        let mirror = "https://mirror.example.com/Miniforge3-Linux-x86_64.sh";
+        let fs = RealFileSystem;
+
+        // A local installer wins over a mirror
+        assert_eq!(
+            select_installer_source(Some(&installer_str), Some(mirror), &fs),
+            Ok(InstallerSource::Local(installer.clone()))
+        );
+        assert_eq!(
+            select_installer_source(None, Some(mirror), &fs),
+            Ok(InstallerSource::Url(mirror.to_string()))
+        );
+        assert_eq!(
+            select_installer_source(Some("  "), Some(""), &fs),
+            Ok(InstallerSource::Default)
+        );
+        assert_eq!(
+            select_installer_source(None, None, &fs),
+            Ok(InstallerSource::Default)
+        );
+
+        let missing = dir.join("missing.sh").to_string_lossy().to_string();
+        assert!(
+            select_installer_source(Some(&missing), Some(mirror), &fs)
+                .unwrap_err()
+                .contains("not found")
+        );
+        assert!(
+            select_installer_source(Some(&dir.to_string_lossy()), None, &fs)
+                .unwrap_err()
+                .contains("not a file")
+        );
+        assert!(select_installer_source(None, Some("ftp://mirror.example.com/x.sh"), &fs).is_err());
+        assert!(
+            select_installer_source(None, Some("http://mirror.example.com/x.sh"), &fs)
+                .unwrap_err()
+                .contains("must use https")
+        );
+
+        // Scripts are run through bash, so only the file type matters, not the mode
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&installer, std::fs::Permissions::from_mode(0o644)).unwrap();
+            assert_eq!(
+                select_installer_source(Some(&installer_str), None, &fs),
+                Ok(InstallerSource::Local(installer.clone()))
+            );
+        }
+        let other = dir.join("notes.txt");
+        std::fs::write(&other, "").unwrap();
+        assert!(
+            select_installer_source(Some(&other.to_string_lossy()), None, &fs)
+                .unwrap_err()
+                .starts_with("Installer must be a .")
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_scale_progress_maps_into_step_range() {
+        assert_eq!(scale_progress(0.55, 0.9, 0.0), 0.55);
+        assert_eq!(scale_progress(0.55, 0.9, 100.0), 0.9);
+        assert!((scale_progress(0.8, 0.95, 50.0) - 0.875).abs() < 1e-6);
+        // Out-of-range percentages stay inside the step
+        assert_eq!(scale_progress(0.8, 0.95, 250.0), 0.95);
+        assert_eq!(scale_progress(0.8, 0.95, -5.0), 0.8);
+    }
+
+    #[test]
+    fn test_step_progress_never_moves_back_between_package_bars() {
+        let step = StepProgress::new(0.8, 0.95);
+        let lines = [
+            // openbb-core downloads to completion
+            "   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ 8.0/16.0 MB 5.1 MB/s eta 0:00:02",
+            "   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ 16.0/16.0 MB 5.1 MB/s eta 0:00:00",
+            // then the next package's bar starts from the beginning
+            "   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ 1.0/4.0 MB 2.0 MB/s eta 0:00:02",
+            "   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ 4.0/4.0 MB 2.0 MB/s eta 0:00:00",
+        ];
+        let emitted: Vec<Option<f32>> = lines
+            .iter()
+            .map(|line| step.advance(parse_install_percent(line).unwrap()))
+            .collect();
+        let halfway = scale_progress(0.8, 0.95, 50.0);
+        assert_eq!(emitted, [Some(halfway), Some(0.95), None, Some(0.95)]);
+    }
+
+    #[test]
+    fn test_classify_volume_from_probe_output() {
+        assert_eq!(
+            classify_volume("linux", "/home/user", "ext4 /dev/nvme0n1p2 /"),
+            VolumeType::Local
+        );
+        assert_eq!(
+            classify_volume("linux", "/home/user", "nfs4 fileserver:/export/home /home"),
+            VolumeType::Network
+        );
+        assert_eq!(
+            classify_volume("linux", "/mnt/share", "cifs //nas/share /mnt/share"),
+            VolumeType::Network
+        );
+        assert_eq!(
+            classify_volume("linux", "/media/usb", "vfat /dev/sdb1 /media/usb"),
+            VolumeType::Removable
+        );
+        assert_eq!(
+            classify_volume("linux", "/home/user", ""),
+            VolumeType::Unknown
+        );
+
+        let df_header = "Filesystem 512-blocks Used Available Capacity Mounted on\n";
+        assert_eq!(
+            classify_volume(
+                "macos",
+                "/Users/me",
+                &format!("{df_header}/dev/disk3s5 965595304 811 147 85% /System/Volumes/Data")
+            ),
+            VolumeType::Local
+        );
+        assert_eq!(
+            classify_volume(
+                "macos",
+                "/Volumes/USB/openbb",
+                &format!("{df_header}/dev/disk4s1 60000 100 59900 1% /Volumes/USB")
+            ),
+            VolumeType::Removable
+        );
+        assert_eq!(
+            classify_volume(
+                "macos",
+                "/Volumes/share",
+                &format!("{df_header}//me@nas/share 60000 100 59900 1% /Volumes/share")
+            ),
+            VolumeType::Network
+        );
+
+        assert_eq!(
+            classify_volume("windows", "C:\\Users\\me", "Fixed\r\n"),
+            VolumeType::Local
+        );
+        assert_eq!(
+            classify_volume("windows", "Z:\\openbb", "Network"),
+            VolumeType::Network
+        );
+        assert_eq!(
+            classify_volume("windows", "E:\\openbb", "Removable"),
+            VolumeType::Removable
+        );
+        assert_eq!(
+            classify_volume("windows", "\\\\server\\share", ""),
+            VolumeType::Network
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_storage_location_impl_warns_for_network_volume() {
+        let mut mock_env = MockEnvSystem::new();
+        mock_env.expect_consts_os().return_const("linux");
+        mock_env
+            .expect_new_command()
+            .with(mockall::predicate::eq("findmnt"))
+            .returning(|_| {
+                // Stand-in for findmnt printing a network mount
+                let mut command = std::process::Command::new("echo");
+                command.args(["nfs4", "fileserver:/export/home", "/home"]);
+                command
+            });
+
+        let mut mock_fs = MockFileSystem::new();
+        mock_fs.expect_exists().return_const(true);
+
+        let report =
+            check_storage_location_impl("/home/user/.openbb_platform", &mock_fs, &mock_env);
+        assert_eq!(report.volume_type, VolumeType::Network);
+        assert!(report.warning.unwrap().contains("network drive"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_storage_location_probes_nearest_existing_folder() {
+        let mut mock_env = MockEnvSystem::new();
+        mock_env.expect_consts_os().return_const("linux");
+        mock_env
+            .expect_new_command()
+            .with(mockall::predicate::eq("findmnt"))
+            .returning(|_| {
+                // Like findmnt, fails for a path that doesn't exist yet
+                let mut command = std::process::Command::new("sh");
+                command.args([
+                    "-c",
+                    "[ \"$5\" = /mnt/share ] && echo nfs4 fileserver:/export /mnt/share",
+                    "sh",
+                ]);
+                command
+            });
+        // Only /mnt/share exists; the install folder below it is created later
+        let mut mock_fs = MockFileSystem::new();
+        mock_fs
+            .expect_exists()
+            .returning(|path| path == Path::new("/mnt/share") || path == Path::new("/"));
+
+        let report = check_storage_location_impl("/mnt/share/OpenBB/new", &mock_fs, &mock_env);
+        assert_eq!(report.path, "/mnt/share/OpenBB/new");
+        assert_eq!(report.volume_type, VolumeType::Network);
+        assert_eq!(
+            nearest_existing_ancestor("/mnt/share/OpenBB/new", &mock_fs),
+            "/mnt/share"
+        );
+    }
+
     fn clear_installation_state() {
         let mut state = INSTALLATION_STATE.lock().unwrap();
         *state = InstallationState::default();
@@ -1554,6 +2501,7 @@ mod tests {
             assert!(!state.is_installing);
             assert!(!state.is_configuring);
             assert!(!state.is_complete);
+            assert_eq!(state.progress, 0.1);
         }
 
         update_installation_state("install", 0.5, "Installing...");
@@ -1581,17 +2529,22 @@ mod tests {
             assert!(!state.is_installing);
             assert!(!state.is_configuring);
             assert!(state.is_complete);
+            assert_eq!(state.progress, 1.0);
         }
         update_installation_state("error", 0.0, "An error occurred");
         {
             let state = INSTALLATION_STATE.lock().unwrap();
             assert_eq!(state.message, "An error occurred");
+            assert_eq!(state.progress, 1.0);
         }
         update_installation_state("abort", 0.0, "Installation cancelled by user");
         {
             let state = INSTALLATION_STATE.lock().unwrap();
             assert_eq!(state.message, "Installation cancelled by user");
+            assert_eq!(state.progress, 0.0);
         }
+        update_installation_state("download", 1.7, "Downloading...");
+        assert_eq!(INSTALLATION_STATE.lock().unwrap().progress, 1.0);
     }
 
     #[test]
@@ -1686,6 +2639,7 @@ mod tests {
         assert!(!state.is_configuring);
         assert!(!state.is_complete);
         assert_eq!(state.message, "");
+        assert_eq!(state.progress, 0.0);
     }
 
     #[test]
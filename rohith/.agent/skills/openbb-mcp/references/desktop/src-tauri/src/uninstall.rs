@@ -1,4 +1,7 @@
 use crate::tauri_handlers::backends::stop_all_backend_services;
+use crate::tauri_handlers::helpers::{
+    RealFileSystem, parse_settings_with_backup, with_system_settings_lock, write_with_backup,
+};
 use crate::tauri_handlers::jupyter::stop_all_jupyter_servers;
 use serde_json::Value;
 use std::fs;
@@ -20,6 +23,7 @@ pub async fn uninstall_application(
 ) -> Result<Option<String>, String> {
     use crate::tauri_handlers::helpers::{RealEnvSystem, RealFileExtTrait, RealFileSystem};
     log::debug!("Starting application uninstallation");
+    let _maintenance = crate::utils::maintenance::enter_maintenance(&app_handle, "uninstall")?;
 
     // Helper function to emit progress events
     let emit_progress = |step: &str| {
@@ -152,58 +156,68 @@ pub async fn uninstall_application(
     if system_settings_path.exists() {
         log::debug!("Removing 'install_settings' key from system_settings.json");
 
-        let content = match fs::read_to_string(&system_settings_path) {
-            Ok(content) => content,
-            Err(e) => {
-                log::warn!("Failed to read system_settings.json: {e}");
-                return Err(format!("Failed to read system_settings.json: {e}"));
-            }
-        };
-
-        let mut json: Value = match serde_json::from_str(&content) {
-            Ok(json) => json,
-            Err(e) => {
-                log::warn!("Failed to parse system_settings.json: {e}");
-                return Err(format!("Failed to parse system_settings.json: {e}"));
-            }
-        };
+        with_system_settings_lock(&platform_dir, &RealFileSystem, &RealFileExtTrait, || {
+            let content = match fs::read_to_string(&system_settings_path) {
+                Ok(content) => content,
+                Err(e) => {
+                    log::warn!("Failed to read system_settings.json: {e}");
+                    return Err(format!("Failed to read system_settings.json: {e}"));
+                }
+            };
+
+            let mut json: Value = match parse_settings_with_backup(
+                &system_settings_path,
+                &content,
+                &RealFileSystem,
+            ) {
+                Ok(json) => json,
+                Err(e) => {
+                    log::warn!("Failed to parse system_settings.json: {e}");
+                    return Err(format!("Failed to parse system_settings.json: {e}"));
+                }
+            };
 
-        if json.is_object()
-            && let Some(obj) = json.as_object_mut()
-        {
-            // Remove YAML files listed in environments
-            if let Some(envs) = obj.get("environments")
-                && let Some(env_map) = envs.as_object()
+            if json.is_object()
+                && let Some(obj) = json.as_object_mut()
             {
-                for (_env_name, env_val) in env_map {
-                    if let Some(env_file) = env_val.get("environment_file").and_then(|v| v.as_str())
-                    {
-                        let env_file_path = Path::new(env_file);
-                        if env_file_path.exists() {
-                            log::debug!(
-                                "Removing environment YAML file: {}",
-                                env_file_path.display()
-                            );
-                            if let Err(e) = fs::remove_file(env_file_path) {
-                                log::warn!(
-                                    "Failed to remove environment YAML file {}: {}",
-                                    env_file_path.display(),
-                                    e
+                // Remove YAML files listed in environments
+                if let Some(envs) = obj.get("environments")
+                    && let Some(env_map) = envs.as_object()
+                {
+                    for (_env_name, env_val) in env_map {
+                        if let Some(env_file) =
+                            env_val.get("environment_file").and_then(|v| v.as_str())
+                        {
+                            let env_file_path = Path::new(env_file);
+                            if env_file_path.exists() {
+                                log::debug!(
+                                    "Removing environment YAML file: {}",
+                                    env_file_path.display()
                                 );
+                                if let Err(e) = fs::remove_file(env_file_path) {
+                                    log::warn!(
+                                        "Failed to remove environment YAML file {}: {}",
+                                        env_file_path.display(),
+                                        e
+                                    );
+                                }
                             }
                         }
                     }
                 }
-            }
-            // Remove the environments key
-            obj.remove("environments");
-            obj.remove("install_settings");
+                // Remove the environments key
+                obj.remove("environments");
+                obj.remove("install_settings");
 
-            let updated_content = serde_json::to_string_pretty(&json).unwrap_or_default();
-            if let Err(e) = fs::write(&system_settings_path, updated_content) {
-                log::warn!("Failed to write updated system_settings.json: {e}");
+                let updated_content = serde_json::to_string_pretty(&json).unwrap_or_default();
+                if let Err(e) =
+                    write_with_backup(&system_settings_path, &updated_content, &RealFileSystem)
+                {
+                    log::warn!("Failed to write updated system_settings.json: {e}");
+                }
             }
-        }
+            Ok(())
+        })?;
     } // STEP 6: Remove settings if requested
     if remove_settings {
         emit_progress("Removing settings directory...");
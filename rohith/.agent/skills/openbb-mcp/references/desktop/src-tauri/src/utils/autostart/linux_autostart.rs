@@ -3,12 +3,37 @@ use std::io::Write;
 use std::path::PathBuf;
 use tauri::AppHandle;
 
+use super::{Autostart, launch_args};
+
+/// XDG autostart entry in `~/.config/autostart`
+pub struct LinuxAutostart(AppHandle);
+
+impl LinuxAutostart {
+    pub fn new(app_handle: &AppHandle) -> Self {
+        Self(app_handle.clone())
+    }
+}
+
+impl Autostart for LinuxAutostart {
+    fn is_enabled(&self) -> Result<bool, String> {
+        is_autostart_enabled(&self.0)
+    }
+
+    fn enable(&self, launch_minimized: bool) -> Result<(), String> {
+        enable_autostart(&self.0, launch_minimized)
+    }
+
+    fn disable(&self) -> Result<(), String> {
+        disable_autostart(&self.0)
+    }
+}
+
 pub fn is_autostart_enabled(app_handle: &AppHandle) -> Result<bool, String> {
     let desktop_file_path = get_autostart_desktop_file_path(app_handle)?;
     Ok(desktop_file_path.exists())
 }
 
-pub fn enable_autostart(app_handle: &AppHandle) -> Result<(), String> {
+pub fn enable_autostart(app_handle: &AppHandle, launch_minimized: bool) -> Result<(), String> {
     let autostart_dir = get_autostart_directory()?;
 
     // Create autostart directory if it doesn't exist
@@ -29,18 +54,12 @@ pub fn enable_autostart(app_handle: &AppHandle) -> Result<(), String> {
     }
 
     // Create desktop entry file
-    let desktop_file_content = format!(
-        r#"[Desktop Entry]
-Type=Application
-Name={}
-Exec="{}"
-Terminal=false
-X-GNOME-Autostart-enabled=true
-"#,
-        app_handle.package_info().name,
+    let desktop_file_content = desktop_entry(
+        &app_handle.package_info().name,
         executable_path
             .to_str()
-            .ok_or("Failed to convert executable path to string")?
+            .ok_or("Failed to convert executable path to string")?,
+        &launch_args(launch_minimized),
     );
 
     // Write desktop file
@@ -78,6 +97,22 @@ pub fn disable_autostart(app_handle: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+fn desktop_entry(app_name: &str, executable_path: &str, args: &[&str]) -> String {
+    let exec = std::iter::once(format!("\"{executable_path}\""))
+        .chain(args.iter().map(|arg| arg.to_string()))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        r#"[Desktop Entry]
+Type=Application
+Name={app_name}
+Exec={exec}
+Terminal=false
+X-GNOME-Autostart-enabled=true
+"#
+    )
+}
+
 fn get_autostart_directory() -> Result<PathBuf, String> {
     // Get user's config directory (usually ~/.config)
     let config_dir = dirs::config_dir().ok_or("Failed to determine user config directory")?;
@@ -92,3 +127,27 @@ fn get_autostart_desktop_file_path(app_handle: &AppHandle) -> Result<PathBuf, St
 
     Ok(autostart_dir.join(format!("{}.desktop", app_name)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desktop_entry_quotes_executable() {
+        let entry = desktop_entry("Open Data Platform", "/opt/Open Data Platform/app", &[]);
+        assert!(entry.starts_with("[Desktop Entry]\n"));
+        assert!(entry.contains("Name=Open Data Platform\n"));
+        assert!(entry.contains("Exec=\"/opt/Open Data Platform/app\"\n"));
+        assert!(entry.contains("X-GNOME-Autostart-enabled=true\n"));
+    }
+
+    #[test]
+    fn test_desktop_entry_embeds_minimized_flag() {
+        let entry = desktop_entry(
+            "Open Data Platform",
+            "/opt/Open Data Platform/app",
+            &launch_args(true),
+        );
+        assert!(entry.contains("Exec=\"/opt/Open Data Platform/app\" --minimized\n"));
+    }
+}
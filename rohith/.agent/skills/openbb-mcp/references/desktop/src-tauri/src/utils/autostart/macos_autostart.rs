@@ -1,8 +1,125 @@
 // This module provides functionality to manage autostart settings on macOS using only system services.
+use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 use tauri::{AppHandle, Runtime};
 
+use super::{Autostart, launch_args};
+
+// Label of the per-user LaunchAgent, also its file name in ~/Library/LaunchAgents
+const LAUNCH_AGENT_LABEL: &str = "co.openbb.platform.autostart";
+
+/// Per-user LaunchAgent that runs the app at login. Unlike login items it can pass
+/// arguments, so `--minimized` reaches the app. Login items added by earlier versions
+/// are still recognised and removed.
+pub struct MacosAutostart(AppHandle);
+
+impl MacosAutostart {
+    pub fn new(app_handle: &AppHandle) -> Self {
+        Self(app_handle.clone())
+    }
+}
+
+impl Autostart for MacosAutostart {
+    fn is_enabled(&self) -> Result<bool, String> {
+        Ok(launch_agent_path()?.exists() || is_login_item_enabled(&self.0)?)
+    }
+
+    fn enable(&self, launch_minimized: bool) -> Result<(), String> {
+        // A leftover login item would launch the app a second time
+        disable_login_item(&self.0)?;
+        enable_launch_agent(launch_minimized)
+    }
+
+    fn disable(&self) -> Result<(), String> {
+        disable_autostart(&self.0)
+    }
+}
+
+/// Remove the LaunchAgent and any login item left by an earlier version
+pub fn disable_autostart<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
+    disable_launch_agent()?;
+    disable_login_item(app_handle)
+}
+
+fn launch_agent_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Failed to determine home directory")?;
+    Ok(home
+        .join("Library")
+        .join("LaunchAgents")
+        .join(format!("{LAUNCH_AGENT_LABEL}.plist")))
+}
+
+// Escape a value for use as XML text in a property list
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn launch_agent_plist(label: &str, executable_path: &str, args: &[&str]) -> String {
+    let program_arguments = std::iter::once(executable_path)
+        .chain(args.iter().copied())
+        .map(|arg| format!("        <string>{}</string>\n", escape_xml(arg)))
+        .collect::<String>();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+{program_arguments}    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>ProcessType</key>
+    <string>Interactive</string>
+</dict>
+</plist>
+"#,
+        escape_xml(label)
+    )
+}
+
+/// Write the LaunchAgent; launchd picks it up at the next login
+pub fn enable_launch_agent(launch_minimized: bool) -> Result<(), String> {
+    let plist_path = launch_agent_path()?;
+    if let Some(dir) = plist_path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create LaunchAgents directory: {e}"))?;
+    }
+
+    let executable_path =
+        std::env::current_exe().map_err(|e| format!("Failed to get executable path: {e}"))?;
+    let plist = launch_agent_plist(
+        LAUNCH_AGENT_LABEL,
+        executable_path
+            .to_str()
+            .ok_or("Failed to convert executable path to string")?,
+        &launch_args(launch_minimized),
+    );
+    fs::write(&plist_path, plist).map_err(|e| format!("Failed to write LaunchAgent: {e}"))?;
+
+    log::debug!("Wrote LaunchAgent to {}", plist_path.display());
+    Ok(())
+}
+
+pub fn disable_launch_agent() -> Result<(), String> {
+    let plist_path = launch_agent_path()?;
+    if plist_path.exists() {
+        fs::remove_file(&plist_path).map_err(|e| format!("Failed to remove LaunchAgent: {e}"))?;
+    }
+    Ok(())
+}
+
+// Escape a value for use inside an AppleScript string literal
+fn escape_applescript(value: &str) -> String {
+    value.replace('\\', r"\\").replace('"', r#"\""#)
+}
+
 // Get application path for macOS - prioritize .app bundle over direct executable
 fn get_app_path<R: Runtime>(_app_handle: &AppHandle<R>) -> Result<PathBuf, String> {
     // First try to find the .app bundle to preserve the icon
@@ -42,74 +159,9 @@ fn get_app_path<R: Runtime>(_app_handle: &AppHandle<R>) -> Result<PathBuf, Strin
     Ok(exe_path)
 }
 
-/// Enable autostart using pure AppleScript on macOS
-pub fn enable_autostart<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
-    // Get the path to the application
-    let app_path = get_app_path(app_handle)?;
-    let app_path_str = app_path
-        .to_str()
-        .ok_or_else(|| "Invalid app path".to_string())?;
-
-    log::debug!("Enabling autostart for: {app_path_str}");
-
-    // First check if it's already in login items
-    let check_script = format!(
-        r#"tell application "System Events"
-            set loginItems to login items
-            repeat with loginItem in loginItems
-                try
-                    set itemPath to path of loginItem as string
-                    if itemPath contains "{}" then
-                        return true
-                    end if
-                end try
-            end repeat
-            return false
-        end tell"#,
-        app_path_str.replace(r#"""#, r#"\""#)
-    );
-
-    let output = Command::new("osascript")
-        .arg("-e")
-        .arg(&check_script)
-        .output()
-        .map_err(|e| format!("Failed to execute AppleScript: {e}"))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    log::debug!("Check if already in login items: {stdout}");
-
-    if stdout == "true" {
-        log::debug!("App is already in login items");
-        return Ok(());
-    }
-
-    // Add to login items with AppleScript - set the display name to "OpenBB Platform" and add --autostart argument
-    let script = format!(
-        r#"tell application "System Events"
-            make new login item at end with properties {{path:"{}", hidden:false, name:"OpenBB Platform"}}
-        end tell"#,
-        app_path_str.replace(r#"""#, r#"\""#)
-    );
-
-    let output = Command::new("osascript")
-        .arg("-e")
-        .arg(&script)
-        .output()
-        .map_err(|e| format!("Failed to execute AppleScript: {e}"))?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        log::error!("AppleScript error: {error}");
-        return Err(format!("Failed to add to login items: {error}"));
-    }
-
-    log::debug!("Successfully added to login items");
-    Ok(())
-}
-
-/// Disable autostart using pure AppleScript on macOS
-pub fn disable_autostart<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
-    log::debug!("Disabling autostart");
+/// Remove login items added by versions that registered through System Events
+pub fn disable_login_item<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
+    log::debug!("Removing login items");
 
     // Get the app path to identify it in login items
     let app_path = get_app_path(app_handle)?;
@@ -146,7 +198,7 @@ pub fn disable_autostart<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), St
 
         return count of itemsToRemove
         end tell"#,
-        app_path_str.replace(r#"""#, r#"\""#)
+        escape_applescript(app_path_str)
     );
 
     let output = Command::new("osascript")
@@ -168,8 +220,8 @@ pub fn disable_autostart<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), St
     Ok(())
 }
 
-/// Check if autostart is enabled using pure AppleScript on macOS
-pub fn is_autostart_enabled<R: Runtime>(app_handle: &AppHandle<R>) -> Result<bool, String> {
+/// Check for a login item added by an earlier version, using AppleScript
+pub fn is_login_item_enabled<R: Runtime>(app_handle: &AppHandle<R>) -> Result<bool, String> {
     // Get the app path to identify it in login items
     let app_path = get_app_path(app_handle)?;
     let app_path_str = app_path
@@ -192,7 +244,7 @@ pub fn is_autostart_enabled<R: Runtime>(app_handle: &AppHandle<R>) -> Result<boo
             end repeat
             return false
         end tell"#,
-        app_path_str.replace(r#"""#, r#"\""#)
+        escape_applescript(app_path_str)
     );
 
     let output = Command::new("osascript")
@@ -213,3 +265,33 @@ pub fn is_autostart_enabled<R: Runtime>(app_handle: &AppHandle<R>) -> Result<boo
 
     Ok(is_enabled)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_agent_passes_minimized_argument() {
+        let plist = launch_agent_plist(
+            LAUNCH_AGENT_LABEL,
+            "/Applications/R&D <Tools>/OpenBB Platform.app/Contents/MacOS/openbb-platform",
+            &launch_args(true),
+        );
+        assert!(plist.contains("<string>co.openbb.platform.autostart</string>"));
+        assert!(plist.contains(
+            "<string>/Applications/R&amp;D &lt;Tools&gt;/OpenBB Platform.app/Contents/MacOS/openbb-platform</string>\n        <string>--minimized</string>\n    </array>"
+        ));
+        assert!(plist.contains("<key>RunAtLoad</key>\n    <true/>"));
+
+        let plist = launch_agent_plist(LAUNCH_AGENT_LABEL, "/bin/app", &launch_args(false));
+        assert!(!plist.contains("--minimized"));
+    }
+
+    #[test]
+    fn test_escape_applescript() {
+        assert_eq!(
+            escape_applescript(r#"/Applications/My "Apps"\Open.app"#),
+            r#"/Applications/My \"Apps\"\\Open.app"#
+        );
+    }
+}
@@ -1,3 +1,12 @@
+use crate::tauri_handlers::helpers::{
+    EnvSystem, FileSystem, RealEnvSystem, RealFileSystem, set_user_preference, user_preferences,
+};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tauri::menu::CheckMenuItem;
+use tauri::{Manager, Wry};
+
 #[cfg(target_os = "macos")]
 pub mod macos_autostart;
 
@@ -6,3 +15,327 @@ pub mod linux_autostart;
 
 #[cfg(target_os = "windows")]
 pub mod windows_autostart;
+
+/// Passed by the login launch entry so the app starts in the tray without its window
+pub const MINIMIZED_ARG: &str = "--minimized";
+
+/// Arguments the login launch entry passes to the app
+pub fn launch_args(launch_minimized: bool) -> Vec<&'static str> {
+    if launch_minimized {
+        vec![MINIMIZED_ARG]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Whether this process was started by a launch entry asking for the tray only
+pub fn launched_minimized() -> bool {
+    std::env::args().skip(1).any(|arg| arg == MINIMIZED_ARG)
+}
+
+/// Launch-at-login registration, implemented once per platform
+#[cfg_attr(test, mockall::automock)]
+pub trait Autostart {
+    fn is_enabled(&self) -> Result<bool, String>;
+    fn enable(&self, launch_minimized: bool) -> Result<(), String>;
+    fn disable(&self) -> Result<(), String>;
+
+    /// Whether this platform can launch the app at login at all
+    fn supported(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub use macos_autostart::MacosAutostart as PlatformAutostart;
+
+#[cfg(target_os = "linux")]
+pub use linux_autostart::LinuxAutostart as PlatformAutostart;
+
+#[cfg(target_os = "windows")]
+pub use windows_autostart::WindowsAutostart as PlatformAutostart;
+
+/// Fallback for targets without a launch-at-login mechanism
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub struct PlatformAutostart;
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+impl PlatformAutostart {
+    pub fn new(_app_handle: &AppHandle) -> Self {
+        Self
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+impl Autostart for PlatformAutostart {
+    fn is_enabled(&self) -> Result<bool, String> {
+        Ok(false)
+    }
+
+    fn enable(&self, _launch_minimized: bool) -> Result<(), String> {
+        Err("Autostart is not supported on this platform".to_string())
+    }
+
+    fn disable(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn supported(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AutostartStatus {
+    pub enabled: bool,
+    pub supported: bool,
+    pub launch_minimized: bool,
+}
+
+/// The tray's "Start at Login" item, kept in state so other callers can update its check
+pub struct AutostartMenuItem(pub CheckMenuItem<Wry>);
+
+// Held while the login entry is rewritten, so a disable and a quick re-enable from the
+// tray and the settings page can't interleave and leave the entry removed
+static AUTOSTART_TOGGLE: Mutex<()> = Mutex::new(());
+
+/// Whether login launches start in the tray, stored as `preferences.launch_minimized`
+pub fn launch_minimized_impl<F: FileSystem, E: EnvSystem>(fs: &F, env_sys: &E) -> bool {
+    user_preferences(fs, env_sys)["launch_minimized"]
+        .as_bool()
+        .unwrap_or(false)
+}
+
+pub fn set_launch_minimized_impl<F: FileSystem, E: EnvSystem>(
+    enabled: bool,
+    fs: &F,
+    env_sys: &E,
+) -> Result<(), String> {
+    set_user_preference("launch_minimized", serde_json::json!(enabled), fs, env_sys)
+}
+
+pub fn get_autostart_status_impl<A: Autostart>(
+    autostart: &A,
+    launch_minimized: bool,
+) -> Result<AutostartStatus, String> {
+    let supported = autostart.supported();
+    let enabled = supported && autostart.is_enabled()?;
+    Ok(AutostartStatus {
+        enabled,
+        supported,
+        launch_minimized,
+    })
+}
+
+pub fn set_autostart_impl<A: Autostart>(
+    autostart: &A,
+    enabled: bool,
+    launch_minimized: bool,
+) -> Result<AutostartStatus, String> {
+    if !autostart.supported() {
+        return Err("Autostart is not supported on this platform".to_string());
+    }
+
+    log::debug!(
+        "Attempting to {} autostart",
+        if enabled { "enable" } else { "disable" }
+    );
+    if enabled {
+        autostart.enable(launch_minimized)?;
+    } else {
+        autostart.disable()?;
+    }
+
+    let status = get_autostart_status_impl(autostart, launch_minimized)?;
+    if status.enabled != enabled {
+        return Err(format!(
+            "Autostart is still {} after the change",
+            if status.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        ));
+    }
+    Ok(status)
+}
+
+/// Store the start-minimized preference, re-registering an existing login entry so
+/// its arguments follow
+pub fn set_launch_minimized_for_autostart<A: Autostart, F: FileSystem, E: EnvSystem>(
+    autostart: &A,
+    launch_minimized: bool,
+    fs: &F,
+    env_sys: &E,
+) -> Result<AutostartStatus, String> {
+    set_launch_minimized_impl(launch_minimized, fs, env_sys)?;
+
+    let status = get_autostart_status_impl(autostart, launch_minimized)?;
+    if !status.enabled {
+        return Ok(status);
+    }
+    autostart.disable()?;
+    set_autostart_impl(autostart, true, launch_minimized)
+}
+
+/// Enable or disable launch at login and sync the tray's check mark
+pub fn set_autostart_for_app(
+    app_handle: &AppHandle,
+    enabled: bool,
+) -> Result<AutostartStatus, String> {
+    let _toggle = AUTOSTART_TOGGLE.lock().unwrap();
+    let launch_minimized = launch_minimized_impl(&RealFileSystem, &RealEnvSystem);
+    let status = set_autostart_impl(
+        &PlatformAutostart::new(app_handle),
+        enabled,
+        launch_minimized,
+    )
+    .map_err(|e| {
+        format!(
+            "Failed to {} autostart: {e}",
+            if enabled { "enable" } else { "disable" }
+        )
+    })?;
+
+    if let Some(item) = app_handle.try_state::<AutostartMenuItem>()
+        && let Err(e) = item.0.set_checked(status.enabled)
+    {
+        log::error!("Failed to update menu item state: {e}");
+    }
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn get_autostart_status(app_handle: AppHandle) -> Result<AutostartStatus, String> {
+    get_autostart_status_impl(
+        &PlatformAutostart::new(&app_handle),
+        launch_minimized_impl(&RealFileSystem, &RealEnvSystem),
+    )
+}
+
+#[tauri::command]
+pub fn set_autostart(app_handle: AppHandle, enabled: bool) -> Result<AutostartStatus, String> {
+    set_autostart_for_app(&app_handle, enabled)
+}
+
+/// Start login launches in the tray without showing the window
+#[tauri::command]
+pub fn set_launch_minimized(
+    app_handle: AppHandle,
+    enabled: bool,
+) -> Result<AutostartStatus, String> {
+    let _toggle = AUTOSTART_TOGGLE.lock().unwrap();
+    set_launch_minimized_for_autostart(
+        &PlatformAutostart::new(&app_handle),
+        enabled,
+        &RealFileSystem,
+        &RealEnvSystem,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tauri_handlers::helpers::{MockEnvSystem, MockFileSystem};
+    use mockall::predicate::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_set_autostart_dispatches_and_reports_status() {
+        let mut autostart = MockAutostart::new();
+        autostart.expect_supported().return_const(true);
+        autostart
+            .expect_enable()
+            .with(eq(false))
+            .times(1)
+            .returning(|_| Ok(()));
+        autostart.expect_disable().never();
+        autostart.expect_is_enabled().returning(|| Ok(true));
+
+        assert_eq!(
+            set_autostart_impl(&autostart, true, false),
+            Ok(AutostartStatus {
+                enabled: true,
+                supported: true,
+                launch_minimized: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_set_autostart_fails_when_the_entry_does_not_follow() {
+        let mut autostart = MockAutostart::new();
+        autostart.expect_supported().return_const(true);
+        autostart.expect_enable().times(1).returning(|_| Ok(()));
+        // The entry was removed again right after being written
+        autostart.expect_is_enabled().returning(|| Ok(false));
+
+        assert_eq!(
+            set_autostart_impl(&autostart, true, false),
+            Err("Autostart is still disabled after the change".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unsupported_autostart_is_reported_disabled() {
+        let mut autostart = MockAutostart::new();
+        autostart.expect_supported().return_const(false);
+        autostart.expect_is_enabled().never();
+        autostart.expect_enable().never();
+
+        assert_eq!(
+            get_autostart_status_impl(&autostart, false),
+            Ok(AutostartStatus {
+                enabled: false,
+                supported: false,
+                launch_minimized: false,
+            })
+        );
+        assert!(set_autostart_impl(&autostart, true, false).is_err());
+    }
+
+    #[test]
+    fn test_launch_minimized_reregisters_enabled_entry() {
+        assert_eq!(launch_args(true), vec![MINIMIZED_ARG]);
+        assert!(launch_args(false).is_empty());
+
+        let mut mock_env = MockEnvSystem::new();
+        mock_env
+            .expect_var()
+            .with(eq("HOME"))
+            .returning(|_| Ok("/mock/home".to_string()));
+
+        let stored = Arc::new(Mutex::new(String::from("{}")));
+        let mut mock_fs = MockFileSystem::new();
+        mock_fs.expect_exists().returning(|_| true);
+        mock_fs.expect_read_to_string().returning({
+            let stored = stored.clone();
+            move |_| Ok(stored.lock().unwrap().clone())
+        });
+        mock_fs.expect_write().returning({
+            let stored = stored.clone();
+            move |_, contents| {
+                *stored.lock().unwrap() = contents.to_string();
+                Ok(())
+            }
+        });
+        mock_fs.expect_sync_file().returning(|_| Ok(()));
+        mock_fs.expect_copy().returning(|_, _| Ok(0));
+        mock_fs.expect_rename().returning(|_, _| Ok(()));
+
+        let mut autostart = MockAutostart::new();
+        autostart.expect_supported().return_const(true);
+        autostart.expect_is_enabled().returning(|| Ok(true));
+        autostart.expect_disable().times(1).returning(|| Ok(()));
+        autostart
+            .expect_enable()
+            .with(eq(true))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let status =
+            set_launch_minimized_for_autostart(&autostart, true, &mock_fs, &mock_env).unwrap();
+        assert!(status.enabled && status.launch_minimized);
+        assert!(launch_minimized_impl(&mock_fs, &mock_env));
+    }
+}
@@ -2,6 +2,31 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::AppHandle;
 
+use super::{Autostart, launch_args};
+
+/// Shortcut in the user's Startup folder
+pub struct WindowsAutostart(AppHandle);
+
+impl WindowsAutostart {
+    pub fn new(app_handle: &AppHandle) -> Self {
+        Self(app_handle.clone())
+    }
+}
+
+impl Autostart for WindowsAutostart {
+    fn is_enabled(&self) -> Result<bool, String> {
+        is_autostart_enabled(&self.0)
+    }
+
+    fn enable(&self, launch_minimized: bool) -> Result<(), String> {
+        enable_autostart(&self.0, launch_minimized)
+    }
+
+    fn disable(&self) -> Result<(), String> {
+        disable_autostart(&self.0)
+    }
+}
+
 pub fn is_autostart_enabled(app_handle: &AppHandle) -> Result<bool, String> {
     let startup_dir = get_windows_startup_dir()
         .map_err(|e| format!("Failed to get Windows startup directory: {e}"))?;
@@ -12,7 +37,7 @@ pub fn is_autostart_enabled(app_handle: &AppHandle) -> Result<bool, String> {
     Ok(shortcut_path.exists())
 }
 
-pub fn enable_autostart(app_handle: &AppHandle) -> Result<(), String> {
+pub fn enable_autostart(app_handle: &AppHandle, launch_minimized: bool) -> Result<(), String> {
     let startup_dir = get_windows_startup_dir()
         .map_err(|e| format!("Failed to get Windows startup directory: {e}"))?;
 
@@ -66,6 +91,12 @@ pub fn enable_autostart(app_handle: &AppHandle) -> Result<(), String> {
         .chain(std::iter::once(0))
         .collect();
 
+    let wide_args: Vec<u16> = launch_args(launch_minimized)
+        .join(" ")
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+
     unsafe {
         // Initialize COM
         let hr_init = CoInitializeEx(ptr::null_mut(), COINIT_APARTMENTTHREADED);
@@ -95,6 +126,15 @@ pub fn enable_autostart(app_handle: &AppHandle) -> Result<(), String> {
             return Err(format!("Failed to set shortcut path: {hr_set_path:#x}"));
         }
 
+        let hr_set_args = (*shell_link).SetArguments(wide_args.as_ptr());
+        if !SUCCEEDED(hr_set_args) {
+            (*shell_link).Release();
+            CoUninitialize();
+            return Err(format!(
+                "Failed to set shortcut arguments: {hr_set_args:#x}"
+            ));
+        }
+
         let hr_set_show = (*shell_link).SetShowCmd(SW_SHOW);
         if !SUCCEEDED(hr_set_show) {
             (*shell_link).Release();
@@ -160,6 +200,26 @@ fn get_windows_startup_dir() -> Result<PathBuf, String> {
 }
 
 fn get_shortcut_path(app_handle: &AppHandle, startup_dir: &Path) -> Result<PathBuf, String> {
-    let app_name = app_handle.package_info().name.clone();
-    Ok(startup_dir.join(format!("{app_name}.lnk")))
+    Ok(shortcut_file_path(
+        &app_handle.package_info().name,
+        startup_dir,
+    ))
+}
+
+fn shortcut_file_path(app_name: &str, startup_dir: &Path) -> PathBuf {
+    startup_dir.join(format!("{app_name}.lnk"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortcut_lives_in_startup_dir() {
+        let startup_dir = Path::new(r"C:\Users\me\AppData\Roaming\Startup");
+        assert_eq!(
+            shortcut_file_path("Open Data Platform", startup_dir),
+            startup_dir.join("Open Data Platform.lnk")
+        );
+    }
 }
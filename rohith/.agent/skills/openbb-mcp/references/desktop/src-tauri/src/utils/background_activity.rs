@@ -0,0 +1,182 @@
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::watch;
+
+// How long the main window has to stay hidden before background work is paused
+const AUTO_PAUSE_AFTER: Duration = Duration::from_secs(120);
+
+/// Gate for non-essential periodic work such as health polling and resource sampling.
+/// Cleanup and restart handlers never consult it.
+pub struct BackgroundActivity {
+    // (paused by the user, paused because the window is hidden)
+    paused: Mutex<(bool, bool)>,
+    enabled: watch::Sender<bool>,
+}
+
+impl Default for BackgroundActivity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackgroundActivity {
+    pub fn new() -> Self {
+        let (enabled, _) = watch::channel(true);
+        Self {
+            paused: Mutex::new((false, false)),
+            enabled,
+        }
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut (bool, bool))) -> bool {
+        let mut paused = self.paused.lock().unwrap();
+        apply(&mut paused);
+        let enabled = !paused.0 && !paused.1;
+        self.enabled.send_if_modified(|current| {
+            let changed = *current != enabled;
+            *current = enabled;
+            changed
+        });
+        enabled
+    }
+
+    /// Explicitly enable or disable background activity, returning the effective state
+    pub fn set_enabled(&self, enabled: bool) -> bool {
+        self.update(|paused| paused.0 = !enabled)
+    }
+
+    /// Pause or resume because of window visibility, returning the effective state
+    pub fn set_auto_paused(&self, auto_paused: bool) -> bool {
+        self.update(|paused| paused.1 = auto_paused)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        *self.enabled.borrow()
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.enabled.subscribe()
+    }
+}
+
+pub static BACKGROUND_ACTIVITY: Lazy<BackgroundActivity> = Lazy::new(BackgroundActivity::new);
+
+pub fn background_activity() -> &'static BackgroundActivity {
+    &BACKGROUND_ACTIVITY
+}
+
+/// Run `tick` every `interval` while background activity is enabled.
+/// Ticks are skipped while paused and resume once re-enabled; returning false from
+/// `tick` stops the loop.
+pub async fn run_periodic<F, Fut>(
+    mut enabled: watch::Receiver<bool>,
+    interval: Duration,
+    mut tick: F,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    loop {
+        // Park here while paused
+        if enabled.wait_for(|on| *on).await.is_err() {
+            return;
+        }
+
+        tokio::time::sleep(interval).await;
+
+        if !*enabled.borrow() {
+            continue;
+        }
+        if !tick().await {
+            return;
+        }
+    }
+}
+
+/// Pause background activity if the window is still hidden after a grace period
+pub fn schedule_auto_pause<R: tauri::Runtime>(window: tauri::WebviewWindow<R>) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(AUTO_PAUSE_AFTER).await;
+        if !window.is_visible().unwrap_or(true) {
+            log::debug!("Main window hidden, pausing background activity");
+            background_activity().set_auto_paused(true);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn set_background_activity(enabled: bool) -> bool {
+    log::debug!(
+        "Background activity {} by user",
+        if enabled { "enabled" } else { "paused" }
+    );
+    background_activity().set_enabled(enabled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn advance(duration: Duration) {
+        let step = Duration::from_millis(100);
+        let mut elapsed = Duration::ZERO;
+        while elapsed < duration {
+            tokio::time::advance(step).await;
+            tokio::task::yield_now().await;
+            elapsed += step;
+        }
+    }
+
+    #[test]
+    fn test_effective_state_combines_user_and_auto_pause() {
+        let activity = BackgroundActivity::new();
+        assert!(activity.is_enabled());
+
+        assert!(!activity.set_auto_paused(true));
+        // Enabling by the user does not override the hidden-window pause
+        assert!(!activity.set_enabled(true));
+        assert!(activity.set_auto_paused(false));
+
+        assert!(!activity.set_enabled(false));
+        assert!(!activity.set_auto_paused(false));
+        assert!(activity.set_enabled(true));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_pausing_stops_sampling_tick_and_resuming_restarts_it() {
+        let activity = BackgroundActivity::new();
+        let ticks = Arc::new(AtomicUsize::new(0));
+
+        let ticks_clone = ticks.clone();
+        let task = tokio::spawn(run_periodic(
+            activity.subscribe(),
+            Duration::from_secs(1),
+            move || {
+                let ticks = ticks_clone.clone();
+                async move {
+                    ticks.fetch_add(1, Ordering::SeqCst);
+                    true
+                }
+            },
+        ));
+
+        advance(Duration::from_millis(3500)).await;
+        assert!(ticks.load(Ordering::SeqCst) >= 2);
+
+        activity.set_enabled(false);
+        tokio::task::yield_now().await;
+        let paused_at = ticks.load(Ordering::SeqCst);
+        advance(Duration::from_secs(5)).await;
+        assert_eq!(ticks.load(Ordering::SeqCst), paused_at);
+
+        activity.set_enabled(true);
+        advance(Duration::from_millis(2500)).await;
+        assert!(ticks.load(Ordering::SeqCst) > paused_at);
+
+        task.abort();
+    }
+}
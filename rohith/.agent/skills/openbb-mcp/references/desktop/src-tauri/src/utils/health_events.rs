@@ -0,0 +1,297 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::Emitter;
+
+// Consecutive matching checks a service needs before its new state is reported
+const DEFAULT_REQUIRED_CHECKS: u32 = 3;
+// How long results from the per-service pollers are collected before being emitted together
+const HEALTH_FLUSH_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthState {
+    Healthy,
+    Unhealthy,
+    /// Not checked yet, or no longer checked because the service stopped
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HealthTransition {
+    #[serde(rename = "serviceId")]
+    pub service_id: String,
+    pub state: HealthState,
+}
+
+struct ServiceHealth {
+    // Last state sent to the UI
+    reported: Option<HealthState>,
+    // State of the most recent checks and how many in a row agreed on it
+    pending: HealthState,
+    streak: u32,
+}
+
+/// Suppresses flapping by only reporting a service's state once it has been seen
+/// `required_checks` times in a row.
+pub struct HealthDebouncer {
+    required_checks: u32,
+    services: HashMap<String, ServiceHealth>,
+}
+
+impl Default for HealthDebouncer {
+    fn default() -> Self {
+        Self::new(DEFAULT_REQUIRED_CHECKS)
+    }
+}
+
+impl HealthDebouncer {
+    pub fn new(required_checks: u32) -> Self {
+        Self {
+            required_checks: required_checks.max(1),
+            services: HashMap::new(),
+        }
+    }
+
+    pub fn required_checks(&self) -> u32 {
+        self.required_checks
+    }
+
+    pub fn set_required_checks(&mut self, required_checks: u32) {
+        self.required_checks = required_checks.max(1);
+    }
+
+    /// Feed one check result, returning the transition to report if the state settled
+    pub fn record(&mut self, service_id: &str, state: HealthState) -> Option<HealthTransition> {
+        let service = self
+            .services
+            .entry(service_id.to_string())
+            .or_insert(ServiceHealth {
+                reported: None,
+                pending: state,
+                streak: 0,
+            });
+
+        if service.pending == state {
+            service.streak = service.streak.saturating_add(1);
+        } else {
+            service.pending = state;
+            service.streak = 1;
+        }
+
+        if service.streak < self.required_checks || service.reported == Some(state) {
+            return None;
+        }
+        service.reported = Some(state);
+        Some(HealthTransition {
+            service_id: service_id.to_string(),
+            state,
+        })
+    }
+
+    /// Feed the results of one polling round, returning every settled transition so
+    /// they can go out as a single event
+    pub fn record_batch<'a>(
+        &mut self,
+        results: impl IntoIterator<Item = (&'a str, HealthState)>,
+    ) -> Vec<HealthTransition> {
+        results
+            .into_iter()
+            .filter_map(|(service_id, state)| self.record(service_id, state))
+            .collect()
+    }
+
+    /// The last state reported for a service
+    pub fn reported(&self, service_id: &str) -> Option<HealthState> {
+        self.services
+            .get(service_id)
+            .and_then(|service| service.reported)
+    }
+
+    /// Drop a service's history, e.g. after it was stopped or deleted
+    pub fn forget(&mut self, service_id: &str) {
+        self.services.remove(service_id);
+    }
+}
+
+pub static HEALTH_DEBOUNCER: Lazy<Mutex<HealthDebouncer>> =
+    Lazy::new(|| Mutex::new(HealthDebouncer::default()));
+
+/// Check results waiting for the next flush. Each backend is polled by its own task,
+/// so results are collected here to batch the changes of services that flip together.
+#[derive(Default)]
+pub struct PendingHealthResults {
+    results: Vec<(String, HealthState)>,
+}
+
+impl PendingHealthResults {
+    /// Queue a result, returning whether it started a new batch that needs a flush
+    pub fn push(&mut self, service_id: &str, state: HealthState) -> bool {
+        self.results.push((service_id.to_string(), state));
+        self.results.len() == 1
+    }
+
+    /// Take every queued result, in the order they arrived
+    pub fn drain(&mut self) -> Vec<(String, HealthState)> {
+        std::mem::take(&mut self.results)
+    }
+}
+
+static PENDING_HEALTH_RESULTS: Lazy<Mutex<PendingHealthResults>> =
+    Lazy::new(|| Mutex::new(PendingHealthResults::default()));
+
+/// Event carrying a batch of `HealthTransition`s
+pub const HEALTH_CHANGED_EVENT: &str = "backend-health-changed";
+
+fn emit_transitions<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    transitions: &[HealthTransition],
+) {
+    if let Err(e) = app_handle.emit(HEALTH_CHANGED_EVENT, transitions) {
+        log::warn!("Failed to emit {HEALTH_CHANGED_EVENT} event: {e}");
+    }
+}
+
+/// Record a batch of check results and emit the settled transitions as one
+/// `backend-health-changed` event
+fn emit_health_results<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    results: &[(String, HealthState)],
+) {
+    let transitions = HEALTH_DEBOUNCER
+        .lock()
+        .unwrap()
+        .record_batch(results.iter().map(|(id, state)| (id.as_str(), *state)));
+    if transitions.is_empty() {
+        return;
+    }
+
+    log::debug!("Emitting {} backend health transitions", transitions.len());
+    emit_transitions(app_handle, &transitions);
+}
+
+/// Queue one service's check result. The first result of a batch schedules a flush, so
+/// everything that arrives within `HEALTH_FLUSH_DELAY` is reported in a single event.
+pub fn queue_health_result<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    service_id: &str,
+    state: HealthState,
+) {
+    if !PENDING_HEALTH_RESULTS
+        .lock()
+        .unwrap()
+        .push(service_id, state)
+    {
+        return;
+    }
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(HEALTH_FLUSH_DELAY).await;
+        let results = PENDING_HEALTH_RESULTS.lock().unwrap().drain();
+        emit_health_results(&app_handle, &results);
+    });
+}
+
+/// Forget a service that is no longer checked, telling the UI its health is unknown
+/// again if anything had been reported for it
+pub fn clear_health<R: tauri::Runtime>(app_handle: &tauri::AppHandle<R>, service_id: &str) {
+    let mut debouncer = HEALTH_DEBOUNCER.lock().unwrap();
+    let reported = debouncer.reported(service_id);
+    debouncer.forget(service_id);
+    drop(debouncer);
+
+    if reported.is_none_or(|state| state == HealthState::Unknown) {
+        return;
+    }
+    let transition = [HealthTransition {
+        service_id: service_id.to_string(),
+        state: HealthState::Unknown,
+    }];
+    emit_transitions(app_handle, &transition);
+}
+
+/// Set how many consecutive matching checks are needed before a health change is
+/// reported, returning the effective value
+#[tauri::command]
+pub fn set_health_debounce(required_checks: u32) -> u32 {
+    let mut debouncer = HEALTH_DEBOUNCER.lock().unwrap();
+    debouncer.set_required_checks(required_checks);
+    log::debug!(
+        "Backend health changes now need {} consecutive checks",
+        debouncer.required_checks()
+    );
+    debouncer.required_checks()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use HealthState::{Healthy, Unhealthy};
+
+    #[test]
+    fn test_flapping_service_is_suppressed_until_stable() {
+        let mut debouncer = HealthDebouncer::new(3);
+
+        // Settles healthy after three checks
+        assert_eq!(debouncer.record("api", Healthy), None);
+        assert_eq!(debouncer.record("api", Healthy), None);
+        assert_eq!(
+            debouncer.record("api", Healthy),
+            Some(HealthTransition {
+                service_id: "api".to_string(),
+                state: Healthy,
+            })
+        );
+
+        // Flapping never reaches three in a row, so nothing is emitted
+        for state in [Unhealthy, Healthy, Unhealthy, Unhealthy, Healthy, Unhealthy] {
+            assert_eq!(debouncer.record("api", state), None);
+        }
+        assert_eq!(debouncer.record("api", Unhealthy), None);
+        assert_eq!(
+            debouncer.record("api", Unhealthy).map(|t| t.state),
+            Some(Unhealthy)
+        );
+
+        // A stable state is not reported again
+        assert_eq!(debouncer.record("api", Unhealthy), None);
+    }
+
+    #[test]
+    fn test_simultaneous_changes_are_batched() {
+        let mut debouncer = HealthDebouncer::new(2);
+        let round = [("api", Healthy), ("jupyter", Unhealthy), ("mcp", Healthy)];
+
+        assert!(debouncer.record_batch(round).is_empty());
+        let transitions = debouncer.record_batch(round);
+        let ids: Vec<&str> = transitions.iter().map(|t| t.service_id.as_str()).collect();
+        assert_eq!(ids, ["api", "jupyter", "mcp"]);
+
+        assert!(debouncer.record_batch(round).is_empty());
+    }
+
+    #[test]
+    fn test_results_from_separate_pollers_share_one_flush() {
+        let mut pending = PendingHealthResults::default();
+
+        // Only the first result of a batch schedules a flush
+        assert!(pending.push("api", Healthy));
+        assert!(!pending.push("jupyter", Unhealthy));
+        assert!(!pending.push("mcp", Healthy));
+
+        let results = pending.drain();
+        assert_eq!(
+            results,
+            [
+                ("api".to_string(), Healthy),
+                ("jupyter".to_string(), Unhealthy),
+                ("mcp".to_string(), Healthy),
+            ]
+        );
+
+        // Once flushed, the next result starts a new batch
+        assert!(pending.push("api", Unhealthy));
+    }
+}
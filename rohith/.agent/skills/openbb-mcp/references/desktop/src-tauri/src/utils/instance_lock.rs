@@ -0,0 +1,223 @@
+use crate::tauri_handlers::helpers::{
+    EnvSystem, FileExtTrait, FileSystem, RealEnvSystem, RealFileExtTrait, RealFileSystem,
+    get_settings_directory_impl,
+};
+use once_cell::sync::Lazy;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// Held with an exclusive lock for as long as the app runs; the OS drops it on a crash
+const LOCK_FILE: &str = ".instance.lock";
+// PID of the lock holder, kept separate because Windows locks block reading the lock file
+const PID_FILE: &str = ".instance.pid";
+
+/// This instance's hold on the platform directory
+pub struct InstanceLock {
+    file: std::fs::File,
+    pid_path: PathBuf,
+}
+
+pub enum InstanceLockStatus {
+    Acquired(InstanceLock),
+    /// Another live instance holds the lock; its PID if it could be read
+    HeldBy(Option<u32>),
+}
+
+pub fn acquire_instance_lock_impl<F: FileSystem, E: EnvSystem, FE: FileExtTrait>(
+    pid: u32,
+    fs: &F,
+    env_sys: &E,
+    file_ext: &FE,
+) -> Result<InstanceLockStatus, String> {
+    let settings_dir = get_settings_directory_impl(env_sys)?;
+    if !fs.exists(&settings_dir) {
+        fs.create_dir_all(&settings_dir)
+            .map_err(|e| format!("Failed to create settings directory: {e}"))?;
+    }
+
+    let file = fs
+        .open_rw_create(&settings_dir.join(LOCK_FILE))
+        .map_err(|e| format!("Failed to open instance lock: {e}"))?;
+    let pid_path = settings_dir.join(PID_FILE);
+
+    if file_ext.try_lock_exclusive(&file).is_err() {
+        let holder = fs
+            .read_to_string(&pid_path)
+            .ok()
+            .and_then(|content| content.trim().parse().ok());
+        return Ok(InstanceLockStatus::HeldBy(holder));
+    }
+
+    if let Err(e) = fs.write(&pid_path, &pid.to_string()) {
+        let _ = file_ext.unlock(&file);
+        return Err(format!("Failed to write instance PID: {e}"));
+    }
+    Ok(InstanceLockStatus::Acquired(InstanceLock {
+        file,
+        pid_path,
+    }))
+}
+
+pub fn release_instance_lock_impl<F: FileSystem, FE: FileExtTrait>(
+    lock: InstanceLock,
+    fs: &F,
+    file_ext: &FE,
+) -> Result<(), String> {
+    // Remove the PID first so nobody reads it after the lock is gone
+    if let Err(e) = fs.remove_file(&lock.pid_path.to_string_lossy()) {
+        log::warn!("Failed to remove instance PID file: {e}");
+    }
+    file_ext
+        .unlock(&lock.file)
+        .map_err(|e| format!("Failed to unlock instance lock: {e}"))
+}
+
+static INSTANCE_LOCK: Lazy<Mutex<Option<InstanceLock>>> = Lazy::new(|| Mutex::new(None));
+
+/// Take the instance lock for this process if nobody else holds it, returning the
+/// other instance's PID when one does
+pub fn acquire_instance_lock() -> Option<u32> {
+    let mut held = INSTANCE_LOCK.lock().unwrap();
+    if held.is_some() {
+        return None;
+    }
+
+    match acquire_instance_lock_impl(
+        std::process::id(),
+        &RealFileSystem,
+        &RealEnvSystem,
+        &RealFileExtTrait,
+    ) {
+        Ok(InstanceLockStatus::Acquired(lock)) => {
+            log::debug!("Acquired platform directory instance lock");
+            *held = Some(lock);
+            None
+        }
+        Ok(InstanceLockStatus::HeldBy(pid)) => {
+            log::warn!(
+                "Another OpenBB instance (PID {}) is using the same platform directory; settings may be overwritten",
+                pid.map_or_else(|| "unknown".to_string(), |p| p.to_string())
+            );
+            Some(pid.unwrap_or(0))
+        }
+        Err(e) => {
+            log::warn!("Could not check for other instances: {e}");
+            None
+        }
+    }
+}
+
+/// Release the instance lock on exit
+pub fn release_instance_lock() {
+    if let Some(lock) = INSTANCE_LOCK.lock().unwrap().take() {
+        match release_instance_lock_impl(lock, &RealFileSystem, &RealFileExtTrait) {
+            Ok(()) => log::debug!("Released platform directory instance lock"),
+            Err(e) => log::warn!("{e}"),
+        }
+    }
+}
+
+/// PID of another instance sharing this platform directory, if any.
+/// 0 means another instance holds the lock but its PID is unknown.
+#[tauri::command]
+pub fn check_instance_lock() -> Option<u32> {
+    acquire_instance_lock()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tauri_handlers::helpers::{MockEnvSystem, MockFileExtTrait, MockFileSystem};
+    use mockall::predicate::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_instance_lock_acquire_detect_and_release() {
+        let settings_dir = PathBuf::from("/mock/home/.openbb_platform");
+        let pid_path = settings_dir.join(PID_FILE);
+        let lock_path = std::env::temp_dir().join("openbb_instance_lock_test.lock");
+
+        let mut mock_env = MockEnvSystem::new();
+        mock_env
+            .expect_var()
+            .with(eq("HOME"))
+            .returning(|_| Ok("/mock/home".to_string()));
+
+        let stored_pid = Arc::new(Mutex::new(None::<String>));
+        let mut mock_fs = MockFileSystem::new();
+        mock_fs.expect_exists().returning(|_| true);
+        mock_fs
+            .expect_open_rw_create()
+            .with(eq(settings_dir.join(LOCK_FILE)))
+            .returning(move |_| std::fs::File::create(&lock_path));
+        mock_fs
+            .expect_write()
+            .with(eq(pid_path.clone()), always())
+            .returning({
+                let stored_pid = stored_pid.clone();
+                move |_, contents| {
+                    *stored_pid.lock().unwrap() = Some(contents.to_string());
+                    Ok(())
+                }
+            });
+        mock_fs
+            .expect_read_to_string()
+            .with(eq(pid_path.clone()))
+            .returning({
+                let stored_pid = stored_pid.clone();
+                move |_| {
+                    stored_pid
+                        .lock()
+                        .unwrap()
+                        .clone()
+                        .ok_or_else(|| std::io::ErrorKind::NotFound.into())
+                }
+            });
+        mock_fs
+            .expect_remove_file()
+            .with(eq(pid_path.to_string_lossy().to_string()))
+            .returning({
+                let stored_pid = stored_pid.clone();
+                move |_| {
+                    *stored_pid.lock().unwrap() = None;
+                    Ok(())
+                }
+            });
+
+        // Behaves like an OS lock: only one holder until it is unlocked
+        let locked = Arc::new(AtomicBool::new(false));
+        let mut mock_file_ext = MockFileExtTrait::new();
+        mock_file_ext.expect_try_lock_exclusive().returning({
+            let locked = locked.clone();
+            move |_| {
+                if locked.swap(true, Ordering::SeqCst) {
+                    Err(std::io::ErrorKind::WouldBlock.into())
+                } else {
+                    Ok(())
+                }
+            }
+        });
+        mock_file_ext.expect_unlock().returning({
+            let locked = locked.clone();
+            move |_| {
+                locked.store(false, Ordering::SeqCst);
+                Ok(())
+            }
+        });
+
+        let acquire = |pid| acquire_instance_lock_impl(pid, &mock_fs, &mock_env, &mock_file_ext);
+
+        let Ok(InstanceLockStatus::Acquired(first)) = acquire(4242) else {
+            panic!("first instance should acquire the lock");
+        };
+        assert!(matches!(
+            acquire(5151),
+            Ok(InstanceLockStatus::HeldBy(Some(4242)))
+        ));
+
+        release_instance_lock_impl(first, &mock_fs, &mock_file_ext).unwrap();
+        assert!(stored_pid.lock().unwrap().is_none());
+        assert!(matches!(acquire(5151), Ok(InstanceLockStatus::Acquired(_))));
+    }
+}
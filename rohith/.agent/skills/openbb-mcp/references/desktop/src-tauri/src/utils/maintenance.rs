@@ -0,0 +1,205 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+
+/// Returned by mutating commands while an uninstall, repair or relocation runs
+pub const MAINTENANCE_BUSY_ERROR: &str = "busy: maintenance in progress";
+
+#[derive(Clone, Serialize)]
+struct MaintenancePayload {
+    operation: String,
+}
+
+/// Returned when maintenance is requested while other mutating commands still run
+pub const OPERATIONS_RUNNING_ERROR: &str = "busy: other operations are still running";
+
+#[derive(Default)]
+struct MaintenanceState {
+    // The destructive operation in progress, if any
+    operation: Option<String>,
+    // Mutating commands that started before it and haven't finished
+    in_flight: usize,
+}
+
+/// The destructive operation currently running, if any. Other mutating commands are
+/// refused until it finishes so they can't race it, and it can't start while any of
+/// them are still running.
+#[derive(Default)]
+pub struct MaintenanceMode {
+    state: Arc<Mutex<MaintenanceState>>,
+}
+
+impl MaintenanceMode {
+    pub fn current(&self) -> Option<String> {
+        self.state.lock().unwrap().operation.clone()
+    }
+
+    /// Fail with [`MAINTENANCE_BUSY_ERROR`] while maintenance is in progress
+    pub fn ensure_idle(&self) -> Result<(), String> {
+        match self.current() {
+            Some(operation) => {
+                log::warn!("Rejected a mutating command during maintenance ({operation})");
+                Err(MAINTENANCE_BUSY_ERROR.to_string())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Start a mutating command, failing with [`MAINTENANCE_BUSY_ERROR`] during
+    /// maintenance. Maintenance can't be entered until the returned guard is dropped.
+    pub fn begin_operation(&self) -> Result<OperationGuard, String> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(operation) = &state.operation {
+            log::warn!("Rejected a mutating command during maintenance ({operation})");
+            return Err(MAINTENANCE_BUSY_ERROR.to_string());
+        }
+        state.in_flight += 1;
+        Ok(OperationGuard {
+            state: Some(self.state.clone()),
+        })
+    }
+
+    /// Enter maintenance mode until the returned guard is dropped. Only one
+    /// operation can hold it at a time, and only once no mutating command is running.
+    pub fn enter(&self, operation: &str) -> Result<MaintenanceGuard, String> {
+        let mut state = self.state.lock().unwrap();
+        if state.operation.is_some() {
+            return Err(MAINTENANCE_BUSY_ERROR.to_string());
+        }
+        if state.in_flight > 0 {
+            log::warn!(
+                "Refused to start {operation}: {} operations still running",
+                state.in_flight
+            );
+            return Err(OPERATIONS_RUNNING_ERROR.to_string());
+        }
+        state.operation = Some(operation.to_string());
+        log::info!("Entered maintenance mode: {operation}");
+        Ok(MaintenanceGuard {
+            state: self.state.clone(),
+            on_exit: None,
+        })
+    }
+}
+
+/// Marks a mutating command as running until dropped
+#[must_use]
+pub struct OperationGuard {
+    state: Option<Arc<Mutex<MaintenanceState>>>,
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        if let Some(state) = &self.state {
+            let mut state = state.lock().unwrap();
+            state.in_flight = state.in_flight.saturating_sub(1);
+        }
+    }
+}
+
+/// Leaves maintenance mode when dropped, telling the UI if it was told on entry
+#[must_use]
+pub struct MaintenanceGuard {
+    state: Arc<Mutex<MaintenanceState>>,
+    on_exit: Option<Box<dyn FnOnce(String) + Send>>,
+}
+
+impl Drop for MaintenanceGuard {
+    fn drop(&mut self) {
+        let Some(operation) = self.state.lock().unwrap().operation.take() else {
+            return;
+        };
+        log::info!("Left maintenance mode: {operation}");
+        if let Some(on_exit) = self.on_exit.take() {
+            on_exit(operation);
+        }
+    }
+}
+
+/// Enter maintenance mode for `operation` and emit `maintenance-entered` so the UI can
+/// disable its controls; `maintenance-exited` follows when the guard is dropped
+pub fn enter_maintenance<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    operation: &str,
+) -> Result<MaintenanceGuard, String> {
+    let mut guard = app_handle.state::<MaintenanceMode>().enter(operation)?;
+    if let Err(e) = app_handle.emit(
+        "maintenance-entered",
+        MaintenancePayload {
+            operation: operation.to_string(),
+        },
+    ) {
+        log::warn!("Failed to emit maintenance-entered event: {e}");
+    }
+    let app_handle = app_handle.clone();
+    guard.on_exit = Some(Box::new(move |operation| {
+        if let Err(e) = app_handle.emit("maintenance-exited", MaintenancePayload { operation }) {
+            log::warn!("Failed to emit maintenance-exited event: {e}");
+        }
+    }));
+    Ok(guard)
+}
+
+/// Start a mutating command, refusing it while maintenance is in progress. Hold the
+/// guard for as long as the command runs so maintenance waits for it.
+pub fn begin_mutating_command<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+) -> Result<OperationGuard, String> {
+    match app_handle.try_state::<MaintenanceMode>() {
+        Some(mode) => mode.begin_operation(),
+        None => Ok(OperationGuard { state: None }),
+    }
+}
+
+/// The maintenance operation in progress, so a freshly loaded UI starts disabled
+#[tauri::command]
+pub fn get_maintenance_status(mode: tauri::State<'_, MaintenanceMode>) -> Option<String> {
+    mode.current()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutating_command_rejected_during_maintenance() {
+        let mode = MaintenanceMode::default();
+        assert_eq!(mode.ensure_idle(), Ok(()));
+
+        let guard = mode.enter("uninstall").unwrap();
+        assert_eq!(mode.current().as_deref(), Some("uninstall"));
+        assert_eq!(mode.ensure_idle(), Err(MAINTENANCE_BUSY_ERROR.to_string()));
+        assert!(mode.enter("repair").is_err());
+
+        drop(guard);
+        assert_eq!(mode.current(), None);
+        assert_eq!(mode.ensure_idle(), Ok(()));
+    }
+
+    #[test]
+    fn test_maintenance_refused_while_commands_run() {
+        let app = tauri::test::mock_app();
+        app.manage(MaintenanceMode::default());
+        let app_handle = app.handle();
+
+        // A running command keeps maintenance out until it finishes
+        let first = begin_mutating_command(app_handle).unwrap();
+        let second = begin_mutating_command(app_handle).unwrap();
+        assert_eq!(
+            enter_maintenance(app_handle, "uninstall").err().as_deref(),
+            Some(OPERATIONS_RUNNING_ERROR)
+        );
+        drop(first);
+        assert!(enter_maintenance(app_handle, "uninstall").is_err());
+        drop(second);
+
+        // Once in maintenance, new commands are refused until it ends
+        let maintenance = enter_maintenance(app_handle, "remove_environment").unwrap();
+        assert_eq!(
+            begin_mutating_command(app_handle).err().as_deref(),
+            Some(MAINTENANCE_BUSY_ERROR)
+        );
+        drop(maintenance);
+        assert!(begin_mutating_command(app_handle).is_ok());
+    }
+}
@@ -2,6 +2,14 @@
 pub mod app_termination;
 
 pub mod autostart;
+pub mod background_activity;
 pub mod certs;
 pub mod command_sanitizer;
+pub mod health_events;
+pub mod instance_lock;
+pub mod maintenance;
 pub mod process_monitor;
+pub mod sentinel_flags;
+pub mod settings_watcher;
+pub mod shutdown_scheduler;
+pub mod updater;
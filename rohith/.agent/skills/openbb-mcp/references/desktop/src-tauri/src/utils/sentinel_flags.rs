@@ -0,0 +1,138 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Written before an update restart so the relaunched app shows its window
+pub const SHOW_ON_RESTART: &str = ".show_on_restart";
+
+// Every sentinel flag the app writes, so stale ones can be swept at startup
+const KNOWN_FLAGS: &[&str] = &[SHOW_ON_RESTART];
+
+// A flag that was not consumed within this window was left behind by a crash
+const STALE_AFTER: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagState {
+    Fresh,
+    Stale,
+}
+
+fn flag_path(name: &str) -> Option<PathBuf> {
+    let home_dir = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .ok()?;
+    Some(PathBuf::from(home_dir).join(".openbb_platform").join(name))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Decide whether a flag written at `written_at` (unix seconds) can still be acted on
+pub fn classify_flag(written_at: u64, now: u64, max_age: Duration) -> FlagState {
+    // A timestamp in the future means the clock moved; don't trust it
+    if written_at > now || now - written_at > max_age.as_secs() {
+        FlagState::Stale
+    } else {
+        FlagState::Fresh
+    }
+}
+
+// Flags store their creation time; older flags only contain "1", so fall back to mtime
+fn flag_written_at(path: &Path) -> Option<u64> {
+    if let Ok(content) = std::fs::read_to_string(path)
+        && let Ok(timestamp) = content.trim().parse::<u64>()
+        && timestamp > 1
+    {
+        return Some(timestamp);
+    }
+
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+}
+
+fn flag_state(path: &Path) -> Option<FlagState> {
+    if !path.exists() {
+        return None;
+    }
+    Some(match flag_written_at(path) {
+        Some(written_at) => classify_flag(written_at, unix_now(), STALE_AFTER),
+        None => FlagState::Stale,
+    })
+}
+
+/// Write a sentinel flag stamped with the current time
+pub fn set_flag(name: &str) -> Result<(), String> {
+    let path = flag_path(name).ok_or_else(|| "Could not determine home directory".to_string())?;
+    std::fs::write(&path, unix_now().to_string())
+        .map_err(|e| format!("Failed to write flag {name}: {e}"))?;
+    log::debug!("Set sentinel flag {name}");
+    Ok(())
+}
+
+/// Remove a flag and report whether it was present and fresh enough to act on
+pub fn consume_flag(name: &str) -> bool {
+    let Some(path) = flag_path(name) else {
+        return false;
+    };
+    let Some(state) = flag_state(&path) else {
+        return false;
+    };
+
+    let _ = std::fs::remove_file(&path);
+    if state == FlagState::Stale {
+        log::warn!("Ignoring stale sentinel flag {name}");
+        return false;
+    }
+    true
+}
+
+/// Remove stale sentinel flags without acting on them, returning the names removed
+#[tauri::command]
+pub fn cleanup_stale_flags() -> Vec<String> {
+    let mut removed = Vec::new();
+    for name in KNOWN_FLAGS {
+        if let Some(path) = flag_path(name)
+            && flag_state(&path) == Some(FlagState::Stale)
+        {
+            if let Err(e) = std::fs::remove_file(&path) {
+                log::warn!("Failed to remove stale flag {name}: {e}");
+            } else {
+                log::info!("Removed stale sentinel flag {name}");
+                removed.push(name.to_string());
+            }
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_flag_stale_vs_fresh() {
+        let now = 1_700_000_000;
+
+        assert_eq!(classify_flag(now, now, STALE_AFTER), FlagState::Fresh);
+        assert_eq!(classify_flag(now - 60, now, STALE_AFTER), FlagState::Fresh);
+        assert_eq!(
+            classify_flag(now - STALE_AFTER.as_secs(), now, STALE_AFTER),
+            FlagState::Fresh
+        );
+        assert_eq!(
+            classify_flag(now - STALE_AFTER.as_secs() - 1, now, STALE_AFTER),
+            FlagState::Stale
+        );
+        assert_eq!(
+            classify_flag(now - 86_400, now, STALE_AFTER),
+            FlagState::Stale
+        );
+        assert_eq!(classify_flag(now + 60, now, STALE_AFTER), FlagState::Stale);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::tauri_handlers::helpers::{RealEnvSystem, get_settings_directory_impl};
+use notify::{RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+/// Emitted once a settings file has stopped changing for [`SETTINGS_DEBOUNCE`]
+pub const SETTINGS_CHANGED_EVENT: &str = "settings-changed";
+
+/// How long a file must go without further writes before its change is reported
+pub const SETTINGS_DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SettingsChangedPayload {
+    pub file: String,
+}
+
+/// Collapses bursts of filesystem events into one report per file. A file is due
+/// once `window` has passed since its most recent event.
+pub struct SettingsDebouncer {
+    window: Duration,
+    pending: HashMap<String, Instant>,
+}
+
+impl SettingsDebouncer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Note a change to `file`, pushing its report back by another window
+    pub fn record(&mut self, file: &str, now: Instant) {
+        self.pending.insert(file.to_string(), now);
+    }
+
+    /// Files that have been quiet for a full window, in name order; they are forgotten
+    /// until they change again
+    pub fn take_due(&mut self, now: Instant) -> Vec<String> {
+        let mut due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, last)| now.saturating_duration_since(**last) >= self.window)
+            .map(|(file, _)| file.clone())
+            .collect();
+        due.sort();
+        for file in &due {
+            self.pending.remove(file);
+        }
+        due
+    }
+
+    /// How long to wait before the next pending file becomes due
+    pub fn next_timeout(&self, now: Instant) -> Option<Duration> {
+        self.pending
+            .values()
+            .map(|last| (*last + self.window).saturating_duration_since(now))
+            .min()
+    }
+}
+
+// Digest of what the app itself last wrote to each settings file
+static OWN_WRITES: Lazy<Mutex<HashMap<PathBuf, [u8; 32]>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Remember that the app wrote `contents` to `path`, so the watcher doesn't report
+/// the app's own write as an outside change
+pub fn note_own_write(path: &Path, contents: &str) {
+    OWN_WRITES.lock().unwrap().insert(
+        path.to_path_buf(),
+        openssl::sha::sha256(contents.as_bytes()),
+    );
+}
+
+// Whether `path` holds exactly what the app last wrote there
+fn is_own_write(path: &Path, contents: &[u8]) -> bool {
+    OWN_WRITES
+        .lock()
+        .unwrap()
+        .get(path)
+        .is_some_and(|digest| *digest == openssl::sha::sha256(contents))
+}
+
+/// Name of a watched settings file, or None for anything else in the directory.
+/// The `.tmp`, `.bak` and `.lock` files written next to the settings are ignored.
+pub fn settings_file_name(path: &Path) -> Option<String> {
+    if path.extension()? != "json" {
+        return None;
+    }
+    path.file_name()?.to_str().map(str::to_string)
+}
+
+// Feed watcher events through the debouncer until the watcher is dropped
+fn forward_changes(events: Receiver<PathBuf>, settings_dir: PathBuf, app_handle: tauri::AppHandle) {
+    let mut debouncer = SettingsDebouncer::new(SETTINGS_DEBOUNCE);
+    loop {
+        let received = match debouncer.next_timeout(Instant::now()) {
+            Some(timeout) => events.recv_timeout(timeout),
+            None => events.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(path) => {
+                if let Some(file) = settings_file_name(&path) {
+                    debouncer.record(&file, Instant::now());
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        for file in debouncer.take_due(Instant::now()) {
+            // A file that can't be read was most likely removed, which is a change too
+            if std::fs::read(settings_dir.join(&file))
+                .is_ok_and(|contents| is_own_write(&settings_dir.join(&file), &contents))
+            {
+                log::debug!("Settings file written by the app: {file}");
+                continue;
+            }
+            log::debug!("Settings file changed: {file}");
+            if let Err(e) = app_handle.emit(SETTINGS_CHANGED_EVENT, SettingsChangedPayload { file })
+            {
+                log::warn!("Failed to emit {SETTINGS_CHANGED_EVENT} event: {e}");
+            }
+        }
+    }
+    log::debug!("Settings watcher stopped");
+}
+
+/// Watches `~/.openbb_platform/*.json` while the app runs
+#[derive(Default)]
+pub struct SettingsWatcher {
+    watcher: Mutex<Option<notify::RecommendedWatcher>>,
+}
+
+impl SettingsWatcher {
+    pub fn start(&self, app_handle: tauri::AppHandle) -> Result<(), String> {
+        let mut current = self.watcher.lock().unwrap();
+        if current.is_some() {
+            return Ok(());
+        }
+
+        let settings_dir = get_settings_directory_impl(&RealEnvSystem)?;
+        std::fs::create_dir_all(&settings_dir)
+            .map_err(|e| format!("Failed to create settings directory: {e}"))?;
+
+        let (sender, events) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(
+            move |result: notify::Result<notify::Event>| match result {
+                Ok(event) if !event.kind.is_access() => {
+                    for path in event.paths {
+                        let _ = sender.send(path);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Settings watcher error: {e}"),
+            },
+        )
+        .map_err(|e| format!("Failed to create settings watcher: {e}"))?;
+        watcher
+            .watch(&settings_dir, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch {}: {e}", settings_dir.display()))?;
+
+        let watched_dir = settings_dir.clone();
+        std::thread::spawn(move || forward_changes(events, watched_dir, app_handle));
+        log::debug!("Watching settings in {}", settings_dir.display());
+        *current = Some(watcher);
+        Ok(())
+    }
+
+    /// Stop watching. Dropping the watcher closes its channel, which ends the
+    /// forwarding thread.
+    pub fn stop(&self) {
+        if self.watcher.lock().unwrap().take().is_some() {
+            log::debug!("Stopping settings watcher");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debouncer_reports_once_after_writes_settle() {
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        let mut debouncer = SettingsDebouncer::new(Duration::from_millis(300));
+        assert_eq!(debouncer.next_timeout(start), None);
+
+        // A burst of writes keeps pushing the report back
+        debouncer.record("user_settings.json", ms(0));
+        debouncer.record("user_settings.json", ms(100));
+        debouncer.record("user_settings.json", ms(250));
+        assert!(debouncer.take_due(ms(400)).is_empty());
+        assert_eq!(
+            debouncer.next_timeout(ms(400)),
+            Some(Duration::from_millis(150))
+        );
+
+        debouncer.record("system_settings.json", ms(450));
+        assert_eq!(debouncer.take_due(ms(550)), vec!["user_settings.json"]);
+        // Reported files are dropped until they change again
+        assert!(debouncer.take_due(ms(600)).is_empty());
+        assert_eq!(debouncer.take_due(ms(750)), vec!["system_settings.json"]);
+        assert_eq!(debouncer.next_timeout(ms(750)), None);
+
+        // Files that settle together come out in one batch
+        debouncer.record("user_settings.json", ms(800));
+        debouncer.record("system_settings.json", ms(810));
+        assert_eq!(
+            debouncer.take_due(ms(1200)),
+            vec!["system_settings.json", "user_settings.json"]
+        );
+    }
+
+    #[test]
+    fn test_own_writes_are_recognised_by_content() {
+        let path = Path::new("/mock/home/.openbb_platform/own_write_test.json");
+        assert!(!is_own_write(path, b"{}"));
+
+        note_own_write(path, r#"{"a": 1}"#);
+        assert!(is_own_write(path, br#"{"a": 1}"#));
+        // Anything else written afterwards is an outside change
+        assert!(!is_own_write(path, br#"{"a": 2}"#));
+        assert!(!is_own_write(
+            &path.with_file_name("other.json"),
+            br#"{"a": 1}"#
+        ));
+    }
+
+    #[test]
+    fn test_settings_file_name_skips_sidecar_files() {
+        let dir = Path::new("/home/user/.openbb_platform");
+        assert_eq!(
+            settings_file_name(&dir.join("user_settings.json")).as_deref(),
+            Some("user_settings.json")
+        );
+        assert_eq!(
+            settings_file_name(&dir.join("user_settings.json.tmp")),
+            None
+        );
+        assert_eq!(
+            settings_file_name(&dir.join("user_settings.json.bak")),
+            None
+        );
+        assert_eq!(
+            settings_file_name(&dir.join("system_settings.json.lock")),
+            None
+        );
+        assert_eq!(settings_file_name(&dir.join(".env")), None);
+    }
+}
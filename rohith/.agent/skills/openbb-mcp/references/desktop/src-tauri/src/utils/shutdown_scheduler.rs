@@ -0,0 +1,121 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Holds the single pending delayed shutdown, if any
+#[derive(Default)]
+pub struct ShutdownScheduler {
+    pending: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl ShutdownScheduler {
+    pub fn is_scheduled(&self) -> bool {
+        self.pending.lock().unwrap().is_some()
+    }
+
+    /// Cancel the pending shutdown, returning whether one was scheduled
+    pub fn cancel(&self) -> bool {
+        match self.pending.lock().unwrap().take() {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Start a countdown of `delay_secs` that calls `on_tick` with the seconds remaining
+/// once per second and runs `shutdown` when it reaches zero.
+/// Fails if a shutdown is already scheduled.
+pub fn schedule_shutdown_impl<T, S, Fut>(
+    scheduler: &ShutdownScheduler,
+    delay_secs: u64,
+    on_tick: T,
+    shutdown: S,
+) -> Result<(), String>
+where
+    T: Fn(u64) + Send + 'static,
+    S: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut pending = scheduler.pending.lock().unwrap();
+    if pending.is_some() {
+        return Err("A shutdown is already scheduled".to_string());
+    }
+
+    log::info!("Shutdown scheduled in {delay_secs}s");
+    let slot = scheduler.pending.clone();
+    *pending = Some(tokio::spawn(async move {
+        for remaining in (1..=delay_secs).rev() {
+            on_tick(remaining);
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        on_tick(0);
+
+        // Release the slot first so a late cancel can't abort the cleanup half way
+        if slot.lock().unwrap().take().is_none() {
+            return;
+        }
+        log::info!("Scheduled shutdown firing");
+        shutdown().await;
+    }));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    async fn advance(duration: Duration) {
+        let step = Duration::from_millis(100);
+        let mut elapsed = Duration::ZERO;
+        while elapsed < duration {
+            tokio::time::advance(step).await;
+            tokio::task::yield_now().await;
+            elapsed += step;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_schedule_fire_and_cancel_shutdown() {
+        let scheduler = ShutdownScheduler::default();
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        let fired = Arc::new(AtomicBool::new(false));
+
+        let schedule = |scheduler: &ShutdownScheduler| {
+            let ticks = ticks.clone();
+            let fired = fired.clone();
+            schedule_shutdown_impl(
+                scheduler,
+                3,
+                move |remaining| ticks.lock().unwrap().push(remaining),
+                move || async move { fired.store(true, Ordering::SeqCst) },
+            )
+        };
+
+        // Scheduling twice is rejected
+        schedule(&scheduler).unwrap();
+        assert!(scheduler.is_scheduled());
+        assert!(schedule(&scheduler).is_err());
+
+        // Cancelling stops the countdown before it fires
+        advance(Duration::from_millis(1500)).await;
+        assert!(scheduler.cancel());
+        assert!(!scheduler.cancel());
+        advance(Duration::from_secs(5)).await;
+        assert!(!fired.load(Ordering::SeqCst));
+        assert_eq!(*ticks.lock().unwrap(), vec![3, 2]);
+
+        // A fresh schedule runs to completion
+        ticks.lock().unwrap().clear();
+        schedule(&scheduler).unwrap();
+        advance(Duration::from_millis(3500)).await;
+        assert!(fired.load(Ordering::SeqCst));
+        assert_eq!(*ticks.lock().unwrap(), vec![3, 2, 1, 0]);
+        assert!(!scheduler.is_scheduled());
+    }
+}
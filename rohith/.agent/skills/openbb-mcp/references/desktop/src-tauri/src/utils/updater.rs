@@ -0,0 +1,743 @@
+use crate::tauri_handlers::helpers::{
+    EnvSystem, FileSystem, RealEnvSystem, RealFileSystem, get_or_create_app_id,
+    set_user_preference, user_preferences,
+};
+use once_cell::sync::Lazy;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub const UPDATE_MANIFEST_URL: &str =
+    "https://github.com/OpenBB-finance/OpenBB/releases/download/ODP/latest.json";
+
+/// Release track the updater follows, stored as `preferences.update_channel`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    pub fn parse(channel: &str) -> Result<Self, String> {
+        match channel.trim().to_ascii_lowercase().as_str() {
+            "stable" => Ok(UpdateChannel::Stable),
+            "beta" => Ok(UpdateChannel::Beta),
+            _ => Err(format!(
+                "Unknown update channel '{channel}', expected 'stable' or 'beta'"
+            )),
+        }
+    }
+}
+
+/// Manifest of pre-releases, from `preferences.beta_update_manifest_url`. There is no
+/// published beta feed, so the channel only works once one is configured.
+pub fn beta_manifest_url_impl<F: FileSystem, E: EnvSystem>(
+    fs: &F,
+    env_sys: &E,
+) -> Result<String, String> {
+    let url = user_preferences(fs, env_sys)["beta_update_manifest_url"]
+        .as_str()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+        .ok_or("The beta channel needs preferences.beta_update_manifest_url to be set")?;
+    match reqwest::Url::parse(&url) {
+        Ok(parsed) if parsed.scheme() == "https" => Ok(url),
+        Ok(_) => Err(format!("Beta update manifest URL must use https: {url}")),
+        Err(e) => Err(format!("Beta update manifest URL is invalid: {e}")),
+    }
+}
+
+/// Manifest the updater checks for the configured channel. A beta channel without a
+/// usable manifest URL falls back to stable.
+pub fn update_manifest_url_impl<F: FileSystem, E: EnvSystem>(fs: &F, env_sys: &E) -> String {
+    match update_channel_impl(fs, env_sys) {
+        UpdateChannel::Stable => UPDATE_MANIFEST_URL.to_string(),
+        UpdateChannel::Beta => beta_manifest_url_impl(fs, env_sys).unwrap_or_else(|e| {
+            log::warn!("{e}; checking the stable channel");
+            UPDATE_MANIFEST_URL.to_string()
+        }),
+    }
+}
+
+pub fn update_manifest_url() -> String {
+    update_manifest_url_impl(&RealFileSystem, &RealEnvSystem)
+}
+
+/// The configured update channel. Unknown values fall back to stable.
+pub fn update_channel_impl<F: FileSystem, E: EnvSystem>(fs: &F, env_sys: &E) -> UpdateChannel {
+    match user_preferences(fs, env_sys)["update_channel"].as_str() {
+        Some(channel) => UpdateChannel::parse(channel).unwrap_or_else(|e| {
+            log::warn!("{e}; using the stable channel");
+            UpdateChannel::Stable
+        }),
+        None => UpdateChannel::Stable,
+    }
+}
+
+pub fn update_channel() -> UpdateChannel {
+    update_channel_impl(&RealFileSystem, &RealEnvSystem)
+}
+
+pub fn set_update_channel_impl<F: FileSystem, E: EnvSystem>(
+    channel: &str,
+    fs: &F,
+    env_sys: &E,
+) -> Result<UpdateChannel, String> {
+    let channel = UpdateChannel::parse(channel)?;
+    if channel == UpdateChannel::Beta {
+        beta_manifest_url_impl(fs, env_sys)?;
+    }
+    set_user_preference("update_channel", serde_json::json!(channel), fs, env_sys)?;
+    Ok(channel)
+}
+
+/// Payload of the `update-progress` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpdateProgress {
+    pub downloaded: u64,
+    /// Size of the download, if the server sent a content length
+    pub total: Option<u64>,
+    /// `None` while the size is unknown, so the UI shows an indeterminate bar
+    pub percent: Option<f64>,
+}
+
+impl UpdateProgress {
+    pub fn new(downloaded: u64, total: Option<u64>) -> Self {
+        Self {
+            downloaded,
+            total,
+            percent: download_percent(downloaded, total),
+        }
+    }
+}
+
+pub fn download_percent(downloaded: u64, total: Option<u64>) -> Option<f64> {
+    let total = total.filter(|&total| total > 0)?;
+    Some((downloaded as f64 / total as f64 * 100.0).min(100.0))
+}
+
+// Without a content length, progress is reported once per this many bytes
+const UNSIZED_PROGRESS_STEP: u64 = 1024 * 1024;
+
+/// Counts downloaded bytes and yields a progress event only when the whole percent
+/// changes, so the UI isn't flooded with one event per chunk
+#[derive(Debug, Default)]
+pub struct ProgressThrottle {
+    downloaded: u64,
+    last_step: Option<u64>,
+}
+
+impl ProgressThrottle {
+    pub fn advance(&mut self, chunk: u64, total: Option<u64>) -> Option<UpdateProgress> {
+        self.downloaded += chunk;
+        let step = match total.filter(|&total| total > 0) {
+            Some(total) => (self.downloaded.saturating_mul(100) / total).min(100),
+            None => self.downloaded / UNSIZED_PROGRESS_STEP,
+        };
+        if self.last_step == Some(step) {
+            return None;
+        }
+        self.last_step = Some(step);
+        Some(UpdateProgress::new(self.downloaded, total))
+    }
+}
+
+/// How long "Remind me later" keeps the background check from prompting
+pub const REMIND_LATER_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Whether the background check should prompt for `version`, given the version the
+/// user chose to skip and the time (ms) until which they asked not to be reminded
+pub fn should_prompt_for_update(
+    version: &str,
+    skipped_version: Option<&str>,
+    remind_after: Option<i64>,
+    now: i64,
+) -> bool {
+    let normalize = |v: &str| v.trim().trim_start_matches('v').to_string();
+    if skipped_version.is_some_and(|skipped| normalize(skipped) == normalize(version)) {
+        return false;
+    }
+    remind_after.is_none_or(|after| now >= after)
+}
+
+pub fn update_prompt_suppressed_impl<F: FileSystem, E: EnvSystem>(
+    version: &str,
+    now: i64,
+    fs: &F,
+    env_sys: &E,
+) -> bool {
+    let preferences = user_preferences(fs, env_sys);
+    !should_prompt_for_update(
+        version,
+        preferences["skipped_update_version"].as_str(),
+        preferences["update_remind_after"].as_i64(),
+        now,
+    )
+}
+
+/// Whether the background check should stay quiet about `version`
+pub fn update_prompt_suppressed(version: &str) -> bool {
+    update_prompt_suppressed_impl(
+        version,
+        chrono::Utc::now().timestamp_millis(),
+        &RealFileSystem,
+        &RealEnvSystem,
+    )
+}
+
+/// Stop the background check from prompting for `version`
+pub fn skip_update_version(version: &str) -> Result<(), String> {
+    set_user_preference(
+        "skipped_update_version",
+        serde_json::json!(version),
+        &RealFileSystem,
+        &RealEnvSystem,
+    )
+}
+
+/// Keep the background check from prompting for [`REMIND_LATER_PERIOD`]
+pub fn remind_update_later() -> Result<(), String> {
+    let remind_after =
+        chrono::Utc::now().timestamp_millis() + REMIND_LATER_PERIOD.as_millis() as i64;
+    set_user_preference(
+        "update_remind_after",
+        serde_json::json!(remind_after),
+        &RealFileSystem,
+        &RealEnvSystem,
+    )
+}
+
+#[tauri::command]
+pub fn get_update_channel() -> UpdateChannel {
+    update_channel()
+}
+
+/// Switch between stable releases and pre-releases for future update checks
+#[tauri::command]
+pub fn set_update_channel(channel: String) -> Result<(), String> {
+    let channel = set_update_channel_impl(&channel, &RealFileSystem, &RealEnvSystem)?;
+    // An update found on the previous channel must not be installed from this one
+    clear_pending_update();
+    log::info!("Update channel set to {channel:?}");
+    Ok(())
+}
+
+// Upper bound for the diagnostic fetch, so a blackholed endpoint doesn't hang the panel
+const DIAGNOSTIC_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Headers sent with every update check
+pub fn updater_headers() -> Result<HeaderMap, String> {
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static("ODP-Updater"));
+    let app_id = HeaderValue::from_str(&get_or_create_app_id())
+        .map_err(|e| format!("Failed to create X-App-ID header: {e}"))?;
+    headers.insert(HeaderName::from_static("x-app-id"), app_id);
+    Ok(headers)
+}
+
+/// Updater for the configured channel, sending the headers every update check carries
+pub fn build_updater<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+) -> Result<tauri_plugin_updater::Updater, String> {
+    use tauri_plugin_updater::UpdaterExt;
+
+    let headers = updater_headers()?;
+    let url = update_manifest_url()
+        .parse()
+        .map_err(|e| format!("Failed to parse update URL: {e}"))?;
+    app.updater_builder()
+        .headers(headers)
+        .endpoints(vec![url])
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("Failed to build updater: {e}"))
+}
+
+// Release notes longer than this are cut short in the update dialog
+const DIALOG_NOTES_MAX_LINES: usize = 15;
+const DIALOG_NOTES_MAX_CHARS: usize = 1000;
+
+/// Text of the update prompt, with the release notes when the manifest has them.
+/// Long notes are truncated so the native dialog stays on screen.
+pub fn update_dialog_message(version: &str, notes: Option<&str>) -> String {
+    let prompt = format!(
+        "A new version ({version}) is available. Would you like to install it now? The update will close the application and restart."
+    );
+    // Notes written on Windows use CRLF, which would otherwise look longer than what's shown
+    let notes = notes.map(|notes| notes.replace("\r\n", "\n"));
+    let Some(notes) = notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|notes| !notes.is_empty())
+    else {
+        return prompt;
+    };
+
+    let mut shown: String = notes
+        .lines()
+        .take(DIALOG_NOTES_MAX_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if shown.chars().count() > DIALOG_NOTES_MAX_CHARS {
+        shown = shown.chars().take(DIALOG_NOTES_MAX_CHARS).collect();
+    }
+    if shown.len() < notes.len() {
+        shown = format!("{}\n…", shown.trim_end());
+    }
+    format!("{prompt}\n\nWhat's new:\n{shown}")
+}
+
+/// An available update, for the frontend to present in its own UI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub release_notes: Option<String>,
+    pub pub_date: Option<String>,
+    /// Size of the package in bytes, if the download server reports it
+    pub download_size: Option<u64>,
+}
+
+fn update_info(
+    version: &str,
+    body: Option<&str>,
+    manifest: &serde_json::Value,
+    download_size: Option<u64>,
+) -> UpdateInfo {
+    UpdateInfo {
+        version: version.to_string(),
+        release_notes: body
+            .map(str::trim)
+            .filter(|notes| !notes.is_empty())
+            .map(str::to_string),
+        pub_date: manifest["pub_date"].as_str().map(str::to_string),
+        download_size,
+    }
+}
+
+// Content length of the package, without downloading it
+async fn download_size(url: &str) -> Option<u64> {
+    let client = reqwest::Client::builder()
+        .default_headers(updater_headers().ok()?)
+        .timeout(DIAGNOSTIC_TIMEOUT)
+        .build()
+        .ok()?;
+    let response = client.head(url).send().await.ok()?;
+    response
+        .status()
+        .is_success()
+        .then(|| response.content_length())
+        .flatten()
+        .filter(|&size| size > 0)
+}
+
+// Update found by the last `check_for_update`, installed by `apply_update`
+static PENDING_UPDATE: Lazy<Mutex<Option<tauri_plugin_updater::Update>>> =
+    Lazy::new(|| Mutex::new(None));
+
+pub fn take_pending_update() -> Option<tauri_plugin_updater::Update> {
+    PENDING_UPDATE.lock().unwrap().take()
+}
+
+/// Forget the update found by the last check, once it can no longer be trusted
+pub fn clear_pending_update() {
+    *PENDING_UPDATE.lock().unwrap() = None;
+}
+
+/// Check for an update without prompting, so the frontend can show its own UI and
+/// install it with `apply_update`
+#[tauri::command]
+pub async fn check_for_update(app: tauri::AppHandle) -> Result<Option<UpdateInfo>, String> {
+    let update = build_updater(&app)?.check().await.map_err(|e| {
+        clear_pending_update();
+        format!("Failed to check for updates: {e}")
+    })?;
+    let Some(update) = update else {
+        clear_pending_update();
+        return Ok(None);
+    };
+
+    let info = update_info(
+        &update.version,
+        update.body.as_deref(),
+        &update.raw_json,
+        download_size(update.download_url.as_str()).await,
+    );
+    *PENDING_UPDATE.lock().unwrap() = Some(update);
+    Ok(Some(info))
+}
+
+/// Result of fetching and parsing the update manifest without installing anything
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UpdaterDiagnostic {
+    /// The manifest URL answered with a success status
+    pub endpoint_reachable: bool,
+    pub manifest_valid: bool,
+    pub latest_version: Option<String>,
+    pub error: Option<String>,
+}
+
+// Version advertised by an updater manifest, in either the static format (per-platform
+// `platforms` entries) or the dynamic one (a single top-level `url` and `signature`)
+fn parse_update_manifest(body: &str) -> Result<String, String> {
+    let manifest: serde_json::Value =
+        serde_json::from_str(body).map_err(|e| format!("Manifest is not valid JSON: {e}"))?;
+
+    let version = manifest["version"]
+        .as_str()
+        .map(|v| v.trim().trim_start_matches('v'))
+        .filter(|v| !v.is_empty())
+        .ok_or("Manifest has no version")?;
+    if !version
+        .split(['-', '+'])
+        .next()
+        .is_some_and(|core| core.split('.').all(|part| part.parse::<u64>().is_ok()))
+    {
+        return Err(format!(
+            "Manifest version '{version}' is not a valid version"
+        ));
+    }
+
+    let has_download = |entry: &serde_json::Value| {
+        entry["url"].as_str().is_some() && entry["signature"].as_str().is_some()
+    };
+    let valid_download = match manifest["platforms"].as_object() {
+        Some(platforms) => !platforms.is_empty() && platforms.values().all(has_download),
+        None => has_download(&manifest),
+    };
+    if !valid_download {
+        return Err("Manifest is missing download URLs or signatures".to_string());
+    }
+
+    Ok(version.to_string())
+}
+
+pub async fn diagnose_updater_impl(endpoint: &str, headers: HeaderMap) -> UpdaterDiagnostic {
+    let mut diagnostic = UpdaterDiagnostic::default();
+
+    let client = match reqwest::Client::builder()
+        .default_headers(headers)
+        .timeout(DIAGNOSTIC_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            diagnostic.error = Some(format!("Failed to build HTTP client: {e}"));
+            return diagnostic;
+        }
+    };
+
+    let response = match client.get(endpoint).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            diagnostic.error = Some(format!("Update endpoint is unreachable: {e}"));
+            return diagnostic;
+        }
+    };
+    if !response.status().is_success() {
+        diagnostic.error = Some(format!("Update endpoint returned {}", response.status()));
+        return diagnostic;
+    }
+    diagnostic.endpoint_reachable = true;
+
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(e) => {
+            diagnostic.error = Some(format!("Failed to read update manifest: {e}"));
+            return diagnostic;
+        }
+    };
+    match parse_update_manifest(&body) {
+        Ok(version) => {
+            diagnostic.manifest_valid = true;
+            diagnostic.latest_version = Some(version);
+        }
+        Err(e) => diagnostic.error = Some(e),
+    }
+    diagnostic
+}
+
+/// Check that the update endpoint answers and its manifest parses, for the diagnostics panel
+#[tauri::command]
+pub async fn diagnose_updater() -> UpdaterDiagnostic {
+    let headers = match updater_headers() {
+        Ok(headers) => headers,
+        Err(e) => {
+            return UpdaterDiagnostic {
+                error: Some(e),
+                ..Default::default()
+            };
+        }
+    };
+    let diagnostic = diagnose_updater_impl(&update_manifest_url(), headers).await;
+    if let Some(error) = &diagnostic.error {
+        log::warn!("Updater diagnostic failed: {error}");
+    }
+    diagnostic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Minimal HTTP server that answers every request with `status` and `body`
+    async fn manifest_endpoint(status: u16, body: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = [0u8; 1024];
+                    let _ = stream.read(&mut request).await;
+                    let response = format!(
+                        "HTTP/1.1 {status} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        format!("http://{address}/latest.json")
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_updater_impl() {
+        let valid = manifest_endpoint(
+            200,
+            r#"{"version":"v1.1.0","notes":"Fixes","pub_date":"2026-01-01T00:00:00Z","platforms":{"darwin-aarch64":{"signature":"sig","url":"https://example.com/odp.tar.gz"}}}"#,
+        )
+        .await;
+        assert_eq!(
+            diagnose_updater_impl(&valid, HeaderMap::new()).await,
+            UpdaterDiagnostic {
+                endpoint_reachable: true,
+                manifest_valid: true,
+                latest_version: Some("1.1.0".to_string()),
+                error: None,
+            }
+        );
+
+        let missing = manifest_endpoint(404, "Not Found").await;
+        let diagnostic = diagnose_updater_impl(&missing, HeaderMap::new()).await;
+        assert!(!diagnostic.endpoint_reachable);
+        assert!(!diagnostic.manifest_valid);
+        assert!(diagnostic.error.unwrap().contains("404"));
+
+        let malformed = manifest_endpoint(200, r#"{"version": "1.1.0", "platforms": "#).await;
+        let diagnostic = diagnose_updater_impl(&malformed, HeaderMap::new()).await;
+        assert!(diagnostic.endpoint_reachable);
+        assert!(!diagnostic.manifest_valid);
+        assert_eq!(diagnostic.latest_version, None);
+        assert!(
+            diagnostic
+                .error
+                .unwrap()
+                .starts_with("Manifest is not valid JSON")
+        );
+    }
+
+    #[test]
+    fn test_update_channel_selects_manifest_url() {
+        use crate::tauri_handlers::helpers::{MockEnvSystem, MockFileSystem};
+        use mockall::predicate::*;
+
+        let mocks = |settings: &'static str| {
+            let mut mock_env = MockEnvSystem::new();
+            mock_env
+                .expect_var()
+                .with(eq("HOME"))
+                .returning(|_| Ok("/mock/home".to_string()));
+            let mut mock_fs = MockFileSystem::new();
+            mock_fs
+                .expect_read_to_string()
+                .returning(move |_| Ok(settings.to_string()));
+            (mock_fs, mock_env)
+        };
+        let channel_with = |settings: &'static str| {
+            let (mock_fs, mock_env) = mocks(settings);
+            update_channel_impl(&mock_fs, &mock_env)
+        };
+        let manifest_with = |settings: &'static str| {
+            let (mock_fs, mock_env) = mocks(settings);
+            update_manifest_url_impl(&mock_fs, &mock_env)
+        };
+
+        let stable = r#"{"preferences":{"update_channel":"stable"}}"#;
+        assert_eq!(channel_with(stable), UpdateChannel::Stable);
+        assert_eq!(manifest_with(stable), UPDATE_MANIFEST_URL);
+
+        let beta = r#"{"preferences":{"update_channel":"Beta","beta_update_manifest_url":"https://example.com/beta.json"}}"#;
+        assert_eq!(channel_with(beta), UpdateChannel::Beta);
+        assert_eq!(manifest_with(beta), "https://example.com/beta.json");
+
+        // Without a usable beta manifest the updater stays on stable
+        for settings in [
+            r#"{"preferences":{"update_channel":"beta"}}"#,
+            r#"{"preferences":{"update_channel":"beta","beta_update_manifest_url":"http://example.com/beta.json"}}"#,
+        ] {
+            assert_eq!(manifest_with(settings), UPDATE_MANIFEST_URL);
+            let (mock_fs, mock_env) = mocks(settings);
+            assert!(set_update_channel_impl("beta", &mock_fs, &mock_env).is_err());
+        }
+
+        // Missing or unknown channels use stable
+        assert_eq!(channel_with(r#"{"preferences":{}}"#), UpdateChannel::Stable);
+        assert_eq!(
+            channel_with(r#"{"preferences":{"update_channel":"nightly"}}"#),
+            UpdateChannel::Stable
+        );
+        assert!(UpdateChannel::parse("nightly").is_err());
+    }
+
+    #[test]
+    fn test_update_info_from_manifest() {
+        let manifest = serde_json::json!({
+            "version": "1.2.0",
+            "notes": "Fixes",
+            "pub_date": "2026-01-01T00:00:00Z",
+        });
+        assert_eq!(
+            update_info("1.2.0", Some("  Fixes\n"), &manifest, Some(1024)),
+            UpdateInfo {
+                version: "1.2.0".to_string(),
+                release_notes: Some("Fixes".to_string()),
+                pub_date: Some("2026-01-01T00:00:00Z".to_string()),
+                download_size: Some(1024),
+            }
+        );
+
+        let info = update_info("1.2.0", Some(" "), &serde_json::json!({}), None);
+        assert_eq!(info.release_notes, None);
+        assert_eq!(info.pub_date, None);
+    }
+
+    #[test]
+    fn test_update_dialog_message_includes_notes() {
+        let plain = update_dialog_message("1.2.0", None);
+        assert!(plain.starts_with("A new version (1.2.0) is available."));
+        assert_eq!(update_dialog_message("1.2.0", Some("  \n")), plain);
+
+        let message =
+            update_dialog_message("1.2.0", Some("- Faster installs\n- Fixed tray menu\n"));
+        assert_eq!(
+            message,
+            format!("{plain}\n\nWhat's new:\n- Faster installs\n- Fixed tray menu")
+        );
+
+        // Short notes with Windows line endings are shown whole
+        let message =
+            update_dialog_message("1.2.0", Some("- Faster installs\r\n- Fixed tray menu\r\n"));
+        assert_eq!(
+            message,
+            format!("{plain}\n\nWhat's new:\n- Faster installs\n- Fixed tray menu")
+        );
+
+        let long_notes: String = (1..=40).map(|i| format!("- Change {i}\n")).collect();
+        let message = update_dialog_message("1.2.0", Some(&long_notes));
+        assert!(message.contains("- Change 15"));
+        assert!(!message.contains("- Change 16"));
+        assert!(message.ends_with('…'));
+
+        let wide_notes = "é".repeat(3000);
+        let message = update_dialog_message("1.2.0", Some(&wide_notes));
+        assert!(message.ends_with('…'));
+        assert!(message.chars().count() < plain.chars().count() + 1100);
+    }
+
+    #[test]
+    fn test_download_percent() {
+        assert_eq!(download_percent(0, Some(200)), Some(0.0));
+        assert_eq!(download_percent(50, Some(200)), Some(25.0));
+        assert_eq!(download_percent(200, Some(200)), Some(100.0));
+        // Servers can send more than they announced
+        assert_eq!(download_percent(250, Some(200)), Some(100.0));
+        assert_eq!(download_percent(50, None), None);
+        assert_eq!(download_percent(50, Some(0)), None);
+        assert_eq!(
+            UpdateProgress::new(50, None),
+            UpdateProgress {
+                downloaded: 50,
+                total: None,
+                percent: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_progress_throttle() {
+        let mut throttle = ProgressThrottle::default();
+        let emitted: Vec<_> = (0..1000)
+            .filter_map(|_| throttle.advance(10, Some(10_000)))
+            .map(|progress| progress.downloaded)
+            .collect();
+        // One event per whole percent, the first as soon as the download starts
+        assert_eq!(emitted.len(), 101);
+        assert_eq!(emitted.first(), Some(&10));
+        assert_eq!(emitted.last(), Some(&10_000));
+
+        let mut throttle = ProgressThrottle::default();
+        assert!(throttle.advance(512 * 1024, None).is_some());
+        assert!(throttle.advance(256 * 1024, None).is_none());
+        let progress = throttle.advance(256 * 1024, None).unwrap();
+        assert_eq!(progress.downloaded, UNSIZED_PROGRESS_STEP);
+        assert_eq!(progress.percent, None);
+    }
+
+    #[test]
+    fn test_update_prompt_suppression() {
+        let now = 1_700_000_000_000;
+        let day = REMIND_LATER_PERIOD.as_millis() as i64;
+
+        assert!(should_prompt_for_update("1.2.0", None, None, now));
+        // Skipping a version only silences that version
+        assert!(!should_prompt_for_update("1.2.0", Some("1.2.0"), None, now));
+        assert!(!should_prompt_for_update(
+            "1.2.0",
+            Some("v1.2.0"),
+            None,
+            now
+        ));
+        assert!(should_prompt_for_update("1.3.0", Some("1.2.0"), None, now));
+        // Remind later silences every version until the period is over
+        assert!(!should_prompt_for_update(
+            "1.3.0",
+            None,
+            Some(now + day),
+            now
+        ));
+        assert!(!should_prompt_for_update(
+            "1.3.0",
+            None,
+            Some(now + day),
+            now + day - 1
+        ));
+        assert!(should_prompt_for_update(
+            "1.3.0",
+            None,
+            Some(now + day),
+            now + day
+        ));
+        assert!(!should_prompt_for_update(
+            "1.2.0",
+            Some("1.2.0"),
+            Some(now - day),
+            now
+        ));
+    }
+
+    #[test]
+    fn test_parse_update_manifest_formats() {
+        assert_eq!(
+            parse_update_manifest(r#"{"version":"2.0.0","url":"https://x/y","signature":"s"}"#),
+            Ok("2.0.0".to_string())
+        );
+        assert!(parse_update_manifest(r#"{"version":"2.0.0","platforms":{}}"#).is_err());
+        assert!(
+            parse_update_manifest(r#"{"version":"latest","url":"u","signature":"s"}"#).is_err()
+        );
+    }
+}
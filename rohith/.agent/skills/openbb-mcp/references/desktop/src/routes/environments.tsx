@@ -1545,6 +1545,16 @@ end tell
 				}
 			},
 		);
+		const unlistenWarning = await listen<{ processId: string; message: string }>(
+			"process-warning",
+			(event) => {
+				if (event.payload.processId === processId) {
+					creationWarningRef.current = creationWarningRef.current
+						? `${creationWarningRef.current}\n\n${event.payload.message}`
+						: event.payload.message;
+				}
+			},
+		);
 
 		try {
 			setCreationLoading(true);
@@ -1580,9 +1590,12 @@ end tell
 				} catch (extErr) {
 					const errorMsg = String(extErr);
 					console.error("Error installing extensions:", errorMsg);
-					creationWarningRef.current = `Environment '${envNameSnapshot}' created, but some packages failed to install. You can try adding them again from the extensions manager.\n\nDetails: ${extractStderr(
+					const failedExtensions = `Environment '${envNameSnapshot}' created, but some packages failed to install. You can try adding them again from the extensions manager.\n\nDetails: ${extractStderr(
 						errorMsg,
 					)}`;
+					creationWarningRef.current = creationWarningRef.current
+						? `${failedExtensions}\n\n${creationWarningRef.current}`
+						: failedExtensions;
 				}
 			}
 
@@ -1694,6 +1707,7 @@ end tell
 			setCreationComplete(true);
 			setIsCancellingCreation(false);
 			unlisten();
+			unlistenWarning();
 		}
 	};
 
@@ -118,6 +118,22 @@ export default function Setup() {
     setIsLoading(true);
 
     try {
+      // Network and removable volumes work, but slowly; ask before anything is written
+      const reports = await invoke<{ warning?: string }[]>("check_storage_location", {
+        path: data.installDir,
+      });
+      const storageWarning = reports?.[0]?.warning;
+      if (storageWarning) {
+        const proceed = await confirm(`${storageWarning}\n\nContinue with this folder anyway?`, {
+          title: "Slow Installation Location",
+          kind: "warning",
+        });
+        if (!proceed) {
+          isSubmittingRef.current = false;
+          return;
+        }
+      }
+
       await invoke("install_to_directory", {
         directory: data.installDir,
         userDataDirectory: data.userDataDir,
@@ -158,6 +158,30 @@ describe('SetupPage Route', () => {
     expect(mockNavigate).not.toHaveBeenCalled();
   });
 
+  it('stays on setup if the install directory is on a network drive and user cancels', async () => {
+    const warning = '/mock/home/OpenBB is on a network drive.';
+    vi.mocked(invoke).mockImplementation((cmd) => {
+      if (cmd === 'check_directory_exists') return Promise.resolve(false);
+      if (cmd === 'get_home_directory') return Promise.resolve('/mock/home');
+      if (cmd === 'check_storage_location') return Promise.resolve([{ path: '/mock/home/OpenBB', volume_type: 'network', warning }]);
+      return Promise.resolve(undefined);
+    });
+    vi.mocked(confirm).mockResolvedValueOnce(false);
+
+    render(<SetupComponent />);
+    await waitFor(() => screen.getByDisplayValue('/mock/home/OpenBB'));
+    fireEvent.click(screen.getByText(/Begin Installation/i));
+    await waitFor(() =>
+      expect(confirm).toHaveBeenCalledWith(
+        `${warning}\n\nContinue with this folder anyway?`,
+        { title: "Slow Installation Location", kind: "warning" }
+      )
+    );
+    // Nothing is set up for a folder the user turned down
+    expect(vi.mocked(invoke)).not.toHaveBeenCalledWith('install_to_directory', expect.any(Object));
+    expect(mockNavigate).not.toHaveBeenCalled();
+  });
+
   it('displays error message on installation failure', async () => {
     vi.mocked(invoke).mockImplementation((cmd) => {
       if (cmd === 'install_to_directory') return Promise.reject(new Error('Installation failed'));